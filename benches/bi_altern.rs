@@ -67,4 +67,57 @@ fn create_and_count_20000x32(b: &mut Bencher) {
         let iter = BiAltern::new(iter3, iter6);
         black_box(iter.count());
     });
-}
\ No newline at end of file
+}
+
+fn tree_100000x8() -> impl Iterator<Item = usize> {
+    BiAltern::new(BiAltern::new(BiAltern::new(0..100000, 0..100000), BiAltern::new(0..100000, 0..100000)), BiAltern::new(BiAltern::new(0..100000, 0..100000), BiAltern::new(0..100000, 0..100000)))
+}
+
+fn filtered_tree_100000x8() -> impl Iterator<Item = usize> {
+    let leaf = || (0..100000).filter(|value| black_box(*value) != usize::MAX);
+    BiAltern::new(BiAltern::new(BiAltern::new(leaf(), leaf()), BiAltern::new(leaf(), leaf())), BiAltern::new(BiAltern::new(leaf(), leaf()), BiAltern::new(leaf(), leaf())))
+}
+
+#[bench]
+fn create_and_sum_100000x8(b: &mut Bencher) {
+    b.iter(||{
+        black_box(tree_100000x8().sum::<usize>());
+    });
+}
+
+#[bench]
+fn create_and_for_each_100000x8(b: &mut Bencher) {
+    b.iter(||{
+        let mut max = 0;
+        tree_100000x8().for_each(|value| max = max.max(value));
+        black_box(max);
+    });
+}
+
+#[bench]
+fn create_and_nth_100000x8(b: &mut Bencher) {
+    b.iter(||{
+        black_box(tree_100000x8().nth(799990));
+    });
+}
+
+#[bench]
+fn create_and_last_100000x8(b: &mut Bencher) {
+    b.iter(||{
+        black_box(tree_100000x8().last());
+    });
+}
+
+#[bench]
+fn create_and_count_filtered_100000x8(b: &mut Bencher) {
+    b.iter(||{
+        black_box(filtered_tree_100000x8().count());
+    });
+}
+
+#[bench]
+fn create_and_sum_filtered_100000x8(b: &mut Bencher) {
+    b.iter(||{
+        black_box(filtered_tree_100000x8().sum::<usize>());
+    });
+}
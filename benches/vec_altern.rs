@@ -61,4 +61,52 @@ fn create_and_count_20000x32(b: &mut Bencher) {
         }
         black_box(iter.count());
     });
-}
\ No newline at end of file
+}
+
+#[bench]
+fn create_and_count_skewed_20000(b: &mut Bencher) {
+    b.iter(||{
+        let mut iter = VecAltern::with_capacity(20000);
+        for i in 0..20000 {
+            iter.add(0..if i % 100 == 0 { 1000 } else { 1 });
+        }
+        black_box(iter.count());
+    });
+}
+
+fn altern_100000x8() -> VecAltern<'static, usize> {
+    altern!(0..100000, 0..100000, 0..100000, 0..100000, 0..100000, 0..100000, 0..100000, 0..100000)
+}
+
+#[bench]
+fn create_and_sum_100000x8(b: &mut Bencher) {
+    b.iter(||{
+        black_box(altern_100000x8().sum::<usize>());
+    });
+}
+
+#[bench]
+fn create_and_nth_100000x8(b: &mut Bencher) {
+    b.iter(||{
+        black_box(altern_100000x8().nth(799990));
+    });
+}
+
+#[bench]
+fn create_and_count_filtered_100000x8(b: &mut Bencher) {
+    b.iter(||{
+        let mut iter = VecAltern::new();
+        for _ in 0..8 {
+            iter.add((0..100000).filter(|value| black_box(*value) != usize::MAX));
+        }
+        black_box(iter.count());
+    });
+}
+
+#[bench]
+fn create_and_sum_one_long_1000000(b: &mut Bencher) {
+    b.iter(||{
+        let iter = altern!(0..1000000, 0..10, 0..10, 0..10);
+        black_box(iter.sum::<usize>());
+    });
+}
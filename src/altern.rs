@@ -3,40 +3,148 @@
 //! ## When to use what ?
 //!
 //! - `BiAltern` is the most efficient iterator here, in terme of performance, for 2 iterable.<br/>
-//!    But, if you want to traverse more than 2 iterator, is drawback is that it require some reflexion, and manipulation to maybe
-//!    create the iterator that you want (see the section `Common Mistake` in the module `bi_altern`). <br/>
-//!    Futher more, you need to know the number of iterator at compile time for use this one.
-//!    He also implement the `std::iter::traits::ExactSizeIterator` and `std::iter::traits::DoubleEndedIterator` traits if possible.
-//! - `VecAltern` is a little less time perfomant iterator (but still good enough for the majority of usage)`, but more flexible.
-//!    You can easily iterate over as many iterator you want, and add them at runtime, and during the iteration.
+//!   But, if you want to traverse more than 2 iterator, is drawback is that it require some reflexion, and manipulation to maybe
+//!   create the iterator that you want (see the section `Common Mistake` in the module `bi_altern`). <br/>
+//!   Futher more, you need to know the number of iterator at compile time for use this one.
+//!   He also implement the `std::iter::traits::ExactSizeIterator` and `std::iter::traits::DoubleEndedIterator` traits if possible.
+//! - `VecAltern` is a little less time perfomant iterator (but still good enough for the majority of usage), but more flexible.
+//!   You can easily iterate over as many iterator you want, and add them at runtime, and during the iteration.
+//!   `VecAltern::handle` gives a handle to add, pause, resume or remove iterators even while a `for` loop consumes it.
+//!   If all the iterators have the same type, it can store them without boxing (`VecAltern::from(Vec<I>)`), and then
+//!   also implements `ExactSizeIterator` and `DoubleEndedIterator` if possible.
+//!   `SendVecAltern` and `SyncVecAltern` box the iterators as `Send` (and `Sync`), to move the alternation to another
+//!   thread; `altern!(send: ...)` builds one.
+//! - `ArrayAltern` alternates between a fixed number of iterators of the same type, stored in an array.
+//!   It yields the same flat round-robin order as `VecAltern` without any heap allocation, and implements
+//!   `ExactSizeIterator` and `DoubleEndedIterator` if possible.
+//! - `TupleAltern` alternates between a tuple of up to 12 iterators of different types, with the same flat round-robin
+//!   order as `VecAltern`, but with static dispatch and without boxing. It implements the same traits as `BiAltern`.
+//!   The `altern!(static: ...)` macro builds one.
+//! - `RandomAltern` interleaves the iterators in a random order, reproducible from a seed, keeping the order of the
+//!   elements of each iterator.
+//!
+//! All of them implement `SourceTagged` (see the module `source`, and `BiAltern::with_nested_sources` for a tree),
+//! to know from which iterator each element comes, and to group the elements by round with `rounds` (see the module
+//! `rounds`).
+//!
+//! `BiAltern::with_ratio` and `VecAltern::add_weighted` take more elements from some iterators than from others,
+//! in a smooth weighted round-robin order (`a a b a` rather than `a a a b` for a ratio of 3 to 1).
+//!
+//! `BiAltern::chunked` and `VecAltern::with_chunk` take blocks of consecutive elements from each iterator instead
+//! (`a a a b b a a a b b` for chunks of 3 and 2).
+//!
+//! By default, `BiAltern` and `VecAltern` skip an exhausted iterator and keep alternating between the others.
+//! `with_termination(Termination::Shortest)` stops them at the first exhausted iterator instead, like `zip` does,
+//! and `with_complete_rounds()` additionally drops the last incomplete round.
+//!
+//! `BiAltern` and `VecAltern` can `peek` at the next element, and `peek_source` tells from which iterator it comes
+//! (from which source in a tree of nested `BiAltern`), taking into account the iterators found exhausted on the way.
+//! `next_if` consumes it only if it matches.
+//!
+//! `BiAltern` and `VecAltern` can be cloned to replay an alternation, if their iterators can (see `CloneVecAltern` for
+//! boxed iterators), and implement `Debug`. `BiAltern` implements `FusedIterator`, but `VecAltern` does not, as adding
+//! or resuming an iterator restarts it: `VecAltern::into_fused` freezes its iterators into a `FusedVecAltern`.
+//!
+//! When the iterators come from an iterator themselves, `FlattenAltern::flatten_altern` alternates between them,
+//! taking them one at a time when the first round reaches them (see the module `flat_altern`).
+//!
+//! `Unaltern::unaltern(n)` does the opposite, and deals the elements of one iterator into `n` iterators, so that
+//! alternating between them gives back the original order (see the module `dealt`).
+//!
+//! ## Boxed or concrete iterators
+//!
+//! The structures holding any number of iterators in a `Vec` (`VecAltern`, `RandomAltern`, and `VecMerge`, `VecZip`
+//! and `VecDiagonal` in their own modules) box them by default, as `Box<dyn Iterator<Item = A> + 'a>`, so that
+//! iterators of different types can be mixed. Their third type parameter is the type of the stored iterators: built
+//! from a `Vec<I>` (with `From`, or `RandomAltern::seeded`), they store iterators of one concrete type `I` without
+//! boxing them, and implement the traits `I` allows, like `ExactSizeIterator` or `DoubleEndedIterator`.
 //!
 //! ## Performance comparaison
 //!
-//! Here are the result of benchmarks done to compare the speed of each:
-//!
-//! - `BiAltern`: <br/>
-//!     test create_100000x2           ... bench:           0 ns/iter (+/- 0) <br/>
-//!     test create_100000x4           ... bench:           1 ns/iter (+/- 0) <br/>
-//!     test create_and_count_100000x2 ... bench:     213,932 ns/iter (+/- 12,216) <br/>
-//!     test create_and_count_100000x4 ... bench:     667,491 ns/iter (+/- 82,120) <br/>
-//!     test create_and_count_100000x8 ... bench:   2,144,507 ns/iter (+/- 509,807) <br/>
-//!     test create_and_count_20000x32 ... bench:   4,385,295 ns/iter (+/- 1,792,604) <br/>
-//!     test create_and_count_50000x16 ... bench:   4,757,520 ns/iter (+/- 550,491) <br/>
-//! - `VecAltern`: <br/>
-//!     test create_100000x2           ... bench:         157 ns/iter (+/- 55) <br/>
-//!     test create_100000x4           ... bench:         264 ns/iter (+/- 18) <br/>
-//!     test create_and_count_100000x2 ... bench:   1,499,890 ns/iter (+/- 108,683) <br/>
-//!     test create_and_count_100000x4 ... bench:   3,138,175 ns/iter (+/- 266,846) <br/>
-//!     test create_and_count_100000x8 ... bench:   6,195,610 ns/iter (+/- 457,717) <br/>
-//!     test create_and_count_20000x32 ... bench:   4,375,710 ns/iter (+/- 336,647) <br/>
-//!     test create_and_count_50000x16 ... bench:   6,058,750 ns/iter (+/- 372,925) <br/>
+//! Here are the result of benchmarks done to compare the speed of each, from a single run of
+//! `cargo +nightly bench --features nightly`:
+//!
+//! - `BiAltern` (`count` skips whole periods of ranges, see its `nth`; the `filtered` benchmarks use iterators
+//!   without an exact size): <br/>
+//!   test create_100000x2                    ... bench:           2.76 ns/iter (+/- 0.26) <br/>
+//!   test create_100000x4                    ... bench:           8.08 ns/iter (+/- 0.38) <br/>
+//!   test create_and_count_100000x2          ... bench:           8.85 ns/iter (+/- 0.43) <br/>
+//!   test create_and_count_100000x4          ... bench:          64.30 ns/iter (+/- 3.91) <br/>
+//!   test create_and_count_100000x8          ... bench:         222.47 ns/iter (+/- 12.05) <br/>
+//!   test create_and_count_20000x32          ... bench:       1,502.71 ns/iter (+/- 84.70) <br/>
+//!   test create_and_count_50000x16          ... bench:         596.47 ns/iter (+/- 52.43) <br/>
+//!   test create_and_count_filtered_100000x8 ... bench:   7,751,755.90 ns/iter (+/- 700,699.74) <br/>
+//!   test create_and_for_each_100000x8       ... bench:   7,536,356.25 ns/iter (+/- 665,111.99) <br/>
+//!   test create_and_last_100000x8           ... bench:         220.75 ns/iter (+/- 16.94) <br/>
+//!   test create_and_nth_100000x8            ... bench:         166.21 ns/iter (+/- 19.11) <br/>
+//!   test create_and_sum_100000x8            ... bench:   7,814,552.45 ns/iter (+/- 701,450.50) <br/>
+//!   test create_and_sum_filtered_100000x8   ... bench:   7,553,623.40 ns/iter (+/- 613,053.22) <br/>
+//! - `VecAltern` (`count` and `nth` also skip whole periods of iterators with an exact size): <br/>
+//!   test create_100000x2                    ... bench:          80.59 ns/iter (+/- 15.28) <br/>
+//!   test create_100000x4                    ... bench:         112.44 ns/iter (+/- 8.55) <br/>
+//!   test create_and_count_100000x2          ... bench:          98.86 ns/iter (+/- 20.82) <br/>
+//!   test create_and_count_100000x4          ... bench:         181.00 ns/iter (+/- 32.05) <br/>
+//!   test create_and_count_100000x8          ... bench:         323.86 ns/iter (+/- 68.92) <br/>
+//!   test create_and_count_20000x32          ... bench:       1,791.10 ns/iter (+/- 216.49) <br/>
+//!   test create_and_count_50000x16          ... bench:         598.36 ns/iter (+/- 101.78) <br/>
+//!   test create_and_count_filtered_100000x8 ... bench:   2,020,507.20 ns/iter (+/- 109,825.86) <br/>
+//!   test create_and_count_skewed_20000      ... bench:     724,270.50 ns/iter (+/- 37,652.97) <br/>
+//!   test create_and_nth_100000x8            ... bench:         254.76 ns/iter (+/- 14.28) <br/>
+//!   test create_and_sum_100000x8            ... bench:   2,669,736.90 ns/iter (+/- 293,665.20) <br/>
+//!   test create_and_sum_one_long_1000000    ... bench:   1,409,850.52 ns/iter (+/- 193,334.66) <br/>
 //!
 //! The first number precise the size of each iterator, and the second precise the number of iterator.
 
 
 pub mod bi_altern;
 pub mod vec_altern;
+pub mod array_altern;
+pub mod tuple_altern;
+pub mod source;
+pub mod rounds;
+pub mod dealt;
+pub mod flat_altern;
+pub mod random_altern;
 
 pub use bi_altern::BiAltern;
 pub use bi_altern::AlternWith;
-pub use vec_altern::VecAltern;
\ No newline at end of file
+pub use vec_altern::{CloneIterator, CloneVecAltern, FusedVecAltern, PendingResult, SendVecAltern, SyncVecAltern, VecAltern, VecAlternHandle};
+pub use array_altern::ArrayAltern;
+pub use tuple_altern::TupleAltern;
+pub use source::{EnumerateSources, Source, SourceId, SourceTagged};
+pub use rounds::{Round, Rounds};
+pub use dealt::{Blocked, Dealt, TryDealt, Unaltern};
+pub use flat_altern::{FlatAltern, FlattenAltern};
+pub use random_altern::RandomAltern;
+
+/// How an alternation ends when one of its iterators is exhausted.
+///
+/// This is the difference between `Iterator::zip`, which stops with the shortest iterator, and a `zip_longest`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Termination {
+    /// The exhausted iterator is skipped, and the alternation goes on with the others until all are exhausted.
+    #[default]
+    Longest,
+    /// The alternation stops as soon as it is the turn of an exhausted iterator.
+    Shortest,
+}
+
+/// Bounds a size hint by the number of elements left to yield, if any.
+pub(crate) fn limit_hint((lower, upper): (usize, Option<usize>), limit: Option<usize>) -> (usize, Option<usize>) {
+    match limit {
+        None => (lower, upper),
+        Some(limit) => (lower.min(limit), Some(upper.map_or(limit, |upper| upper.min(limit)))),
+    }
+}
+
+/// Bounds a size hint by the elements of the complete rounds of `round` elements, after the `buffered` elements of the
+/// round in progress.
+pub(crate) fn complete_rounds_hint((lower, upper): (usize, Option<usize>), round: usize, buffered: usize) -> (usize, Option<usize>) {
+    let complete = |len: usize| len.checked_div(round).map_or(0, |rounds| rounds * round);
+    (complete(lower).saturating_add(buffered), upper.map(complete).and_then(|upper| upper.checked_add(buffered)))
+}
+
+/// Returns the greatest common divisor of `a` and `b`.
+pub(crate) fn gcd(a: usize, b: usize) -> usize {
+    if b == 0 { a } else { gcd(b, a % b) }
+}
@@ -0,0 +1,197 @@
+//! # ArrayAltern
+//!
+//! The `array_altern` module provides an iterator, `ArrayAltern`, for alternately traversing a fixed number of
+//! iterators of the same type, stored in an array.
+//!
+//! ## Usage
+//!
+//! To use `ArrayAltern`, create a new instance with `ArrayAltern::new([I; N])`.
+//! The `next` method will then yield elements from the N iterators in a round-robin fashion until all iterators are exhausted.<br/>
+//! Unlike nested `BiAltern`s, the order is the flat round-robin order of `VecAltern`, whatever the number of iterators,
+//! and unlike `VecAltern`, nothing is allocated on the heap.<br/>
+//! `ArrayAltern` implements `ExactSizeIterator` if the iterators implement it, and if they additionally implement
+//! `DoubleEndedIterator`, then so does `ArrayAltern`.
+//!
+//! ## Examples
+//!
+//! ```rust
+//! use combin_iterator::altern::ArrayAltern;
+//! let vec1 = vec![1, 4, 7, 9];
+//! let vec2 = vec![2, 5];
+//! let vec3 = vec![3, 6, 8];
+//!
+//! let iter = ArrayAltern::new([vec1.iter(), vec2.iter(), vec3.iter()]);
+//! assert_eq!(iter.collect::<Vec<_>>(), vec![&1, &2, &3, &4, &5, &6, &7, &8, &9]);
+//!
+//! let iter = ArrayAltern::new([vec1.iter(), vec2.iter(), vec3.iter()]);
+//! assert_eq!(iter.len(), 9);
+//! assert_eq!(iter.rev().collect::<Vec<_>>(), vec![&9, &8, &7, &6, &5, &4, &3, &2, &1]);
+//! ```
+
+use super::SourceTagged;
+
+/// ArrayAltern struct, to altern between N iterators of the same type.
+pub struct ArrayAltern<I, const N: usize>
+where
+    I: Iterator
+{
+    iters: [Option<I>; N],
+    current: usize,
+}
+
+impl<I, const N: usize> ArrayAltern<I, N>
+where
+    I: Iterator
+{
+    /// Creates a new instance of an `ArrayAltern` iterator.
+    pub fn new(iters: [I; N]) -> Self {
+        Self {
+            iters: iters.map(Some),
+            current: 0,
+        }
+    }
+
+    /// Returns the indexes of the iterators, in the order they will be visited.
+    fn round(&self) -> impl Iterator<Item = usize> {
+        let current = self.current;
+        (0..N).map(move |offset| (current + offset) % N)
+    }
+}
+
+impl<I, const N: usize> From<[I; N]> for ArrayAltern<I, N>
+where
+    I: Iterator
+{
+    fn from(iters: [I; N]) -> Self {
+        Self::new(iters)
+    }
+}
+
+impl<I, const N: usize> Iterator for ArrayAltern<I, N>
+where
+    I: Iterator
+{
+    type Item = I::Item;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.next_tagged().map(|(_, value)| value)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iters.iter().flatten().fold((0, Some(0)), |(lower, upper), iter| {
+            let hint = iter.size_hint();
+            (lower.saturating_add(hint.0), upper.zip(hint.1).and_then(|(u1, u2)| u1.checked_add(u2)))
+        })
+    }
+}
+
+impl<I, const N: usize> SourceTagged for ArrayAltern<I, N>
+where
+    I: Iterator
+{
+    fn source_count(&self) -> usize {
+        N
+    }
+
+    /// Returns the next element, with the index of its iterator in the array.
+    fn next_tagged(&mut self) -> Option<(usize, Self::Item)> {
+        for _ in 0..N {
+            let index = self.current;
+            self.current = if index + 1 == N { 0 } else { index + 1 };
+            if let Some(iter) = self.iters[index].as_mut() {
+                match iter.next() {
+                    Some(value) => return Some((index, value)),
+                    None => self.iters[index] = None,
+                }
+            }
+        }
+        None
+    }
+}
+
+impl<I, const N: usize> DoubleEndedIterator for ArrayAltern<I, N>
+where
+    I: DoubleEndedIterator + ExactSizeIterator
+{
+    /// Returns the last element of the iteration sequence.
+    ///
+    /// The last element comes from the longest iterator, and if several are as long, from the one
+    /// visited last in the round starting at the current iterator.
+    fn next_back(&mut self) -> Option<Self::Item> {
+        let mut last = None;
+        let mut max_len = 0;
+        for index in self.round() {
+            let len = self.iters[index].as_ref().map_or(0, |iter| iter.len());
+            if len > 0 && len >= max_len {
+                max_len = len;
+                last = Some(index);
+            }
+        }
+        last.and_then(|index| self.iters[index].as_mut()?.next_back())
+    }
+}
+
+impl<I, const N: usize> ExactSizeIterator for ArrayAltern<I, N>
+where
+    I: ExactSizeIterator {}
+
+#[cfg(test)]
+mod tests {
+    use super::ArrayAltern;
+
+    #[test]
+    fn normal_usage() {
+        let vec1 = [1, 4, 7, 9];
+        let vec2 = [2, 5];
+        let vec3 = [3, 6, 8];
+
+        let iter = ArrayAltern::new([vec1.iter(), vec2.iter(), vec3.iter()]);
+
+        assert_eq!(iter.collect::<Vec<_>>(), vec![&1,&2,&3,&4,&5,&6,&7,&8,&9]);
+    }
+
+    #[test]
+    fn empty() {
+        let mut iter = ArrayAltern::<std::ops::Range<u8>, 0>::new([]);
+        assert_eq!(iter.next(), None);
+
+        let mut iter = ArrayAltern::new([0..0, 0..0, 0..0]);
+        assert_eq!(iter.next(), None);
+        assert_eq!(iter.next_back(), None);
+    }
+
+    #[test]
+    fn reverse() {
+        let vec1 = [1, 4, 7, 9];
+        let vec2 = [2, 5];
+        let vec3 = [3, 6, 8];
+
+        let iter = ArrayAltern::new([vec1.iter(), vec2.iter(), vec3.iter()]).rev();
+
+        assert_eq!(iter.collect::<Vec<_>>(), vec![&9,&8,&7,&6,&5,&4,&3,&2,&1]);
+    }
+
+    #[test]
+    fn next_and_next_back() {
+        let vec1 = [1, 4, 7, 9];
+        let vec2 = [2, 5];
+        let vec3 = [3, 6, 8];
+
+        let mut iter = ArrayAltern::new([vec1.iter(), vec2.iter(), vec3.iter()]);
+
+        assert_eq!(iter.next(), Some(&1));
+        assert_eq!(iter.next_back(), Some(&9));
+        assert_eq!(iter.next_back(), Some(&8));
+        assert_eq!(iter.next(), Some(&2));
+        assert_eq!(iter.next(), Some(&3));
+        assert_eq!(iter.next_back(), Some(&7));
+        assert_eq!(iter.len(), 3);
+        assert_eq!(iter.collect::<Vec<_>>(), vec![&4,&5,&6]);
+    }
+
+    #[test]
+    fn len() {
+        let iter = ArrayAltern::new([0..4, 0..2, 0..3, 0..0]);
+        assert_eq!(iter.len(), 9);
+    }
+}
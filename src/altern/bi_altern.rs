@@ -8,7 +8,10 @@
 //! from an `Iterable<Item>`, use `Iterable<Item>::altern_with(Iterable<Item>)`. <br/>
 //! The `next` method will then yield elements from the 2 iterators in a round-robin fashion until both iterators are exhausted.<br/>
 //! `BiAltern`is more complete than `crate::altern::VecAltern`, and implements `core::iter::traits::exact_size::ExactSizeIterator` if both
-//! iterators implement it. Also, if both additionally implement `DoubleEndedIterator`, then so does `BiAltern`.
+//! iterators implement it. Also, if both additionally implement `DoubleEndedIterator`, then so does `BiAltern`.<br/>
+//! To take more elements from one iterator than from the other, use `BiAltern::with_ratio(Iterator<Item>, Iterator<Item>, m, n)`,
+//! which yields `m` elements of the first iterator each time it yields `n` elements of the second one.
+//! To take blocks of consecutive elements instead, use `BiAltern::chunked(Iterator<Item>, Iterator<Item>, k1, k2)`.
 //!
 //! ## Examples
 //!
@@ -77,20 +80,54 @@
 //! let iter = BiAltern::new(BiAltern::new(vec1.iter(), vec3.iter()), BiAltern::new(vec2.iter(), vec4.iter()));
 //! assert_eq!(iter.collect::<Vec<_>>(), vec![&1, &2, &3, &4, &1, &2, &3, &4]);
 //! ```
+//!
+//! ### Sources
+//!
+//! `BiAltern` implements `SourceTagged`, and tells from which iterator each element comes: `0` for the first one and
+//! `1` for the second one.<br/>
+//! With `with_nested_sources`, it tells from which source of its iterators each element comes instead, if they
+//! implement `SourceTagged` too: nested `BiAltern`, and the other iterators wrapped into a `Source`, which is a single
+//! source. The sources of nested `BiAltern` are then numbered from left to right.
+//! ```
+//! use combin_iterator::altern::{BiAltern, Source, SourceTagged};
+//! let vec1 = vec![1, 1];
+//! let vec2 = vec![2, 2];
+//! let vec3 = vec![3, 3, 3];
+//!
+//! let iter = BiAltern::new(vec1.iter(), vec2.iter());
+//! assert_eq!(iter.enumerate_sources().collect::<Vec<_>>(), vec![(0, &1), (1, &2), (0, &1), (1, &2)]);
+//!
+//! let iter = BiAltern::new(BiAltern::new(vec1.iter(), vec2.iter()), Source::new(vec3.iter())).with_nested_sources();
+//! assert_eq!(iter.enumerate_sources().collect::<Vec<_>>(), vec![(0, &1), (2, &3), (1, &2), (2, &3), (0, &1), (2, &3), (1, &2)]);
+//! ```
+
+use std::collections::VecDeque;
+use std::fmt;
+use std::iter::FusedIterator;
+
+use super::{complete_rounds_hint, gcd, limit_hint, SourceTagged, Termination};
 
+/// Calls `next` on the iterator if it is running, and drops it once it returns `None`.
+fn visit<I, T>(iter : &mut Option<I>, next : impl FnOnce(&mut I) -> Option<T>) -> Option<T> {
+    let value = next(iter.as_mut()?);
+    if value.is_none() {
+        *iter = None;
+    }
+    value
+}
 
 /// Trait to convert to a BiAltern iterator
 /// Implemented on Iterator
 pub trait AlternWith<T1 : Iterator<Item = A>, A> {
 
     /// Create the BiAltern, with self and an other iterator
-    fn altern_with<T2: Iterator<Item = A>>(self : Self, other: T2) -> BiAltern<T1, T2, A>
+    fn altern_with<T2: Iterator<Item = A>>(self, other: T2) -> BiAltern<T1, T2, A>
     where
         Self: Sized;
 }
 
 impl<T1 : Iterator<Item = A>, A> AlternWith<T1, A> for T1 {
-    fn altern_with<T2: Iterator<Item = A>>(self : Self, other: T2) -> BiAltern<T1, T2, A>
+    fn altern_with<T2: Iterator<Item = A>>(self, other: T2) -> BiAltern<T1, T2, A>
     where
         Self: Sized {
             BiAltern::new(self, other)
@@ -98,16 +135,61 @@ impl<T1 : Iterator<Item = A>, A> AlternWith<T1, A> for T1 {
 }
 
 /// BiAltern struct, to altern between 2 iterator.
+///
+/// It can be cloned if both iterators can, for instance to replay the same alternation.
+#[derive(Clone)]
 pub struct BiAltern<Iter1 : Iterator, Iter2 : Iterator, Item>
 where
     Iter1: Iterator<Item = Item>,
     Iter2: Iterator<Item = Item>
 {
     iter1 : Option<Iter1>,
-    iter2 :  Option<Iter2>,
+    iter2 : Option<Iter2>,
+    sources : (usize, usize),
+    tags : (Tags<Iter1, Item>, Tags<Iter2, Item>),
     next_is_first : bool,
+    ratio : (usize, usize),
+    credit : isize,
+    chunk : (usize, usize),
+    run : usize,
+    termination : Termination,
+    limit : Option<usize>,
+    /// The elements of the round in progress with `with_complete_rounds`, with their source, if the round is buffered.
+    round : Option<VecDeque<(usize, Item)>>,
+    /// The element taken by `peek`, with its source, if any.
+    peeked : Option<Option<(usize, Item)>>,
+}
+
+/// How the sources of an iterator are counted and tagged: as a single source, or as its own sources with
+/// `with_nested_sources`.
+struct Tags<I, Item> {
+    count : fn(&I) -> usize,
+    next : fn(&mut I) -> Option<(usize, Item)>,
+}
+
+impl<I : Iterator<Item = Item>, Item> Tags<I, Item> {
+    /// Tags the iterator as a single source.
+    fn single() -> Self {
+        Tags { count: |_| 1, next: |iter| iter.next().map(|value| (0, value)) }
+    }
 }
 
+impl<I : SourceTagged<Item = Item>, Item> Tags<I, Item> {
+    /// Tags the iterator with its own sources.
+    fn nested() -> Self {
+        Tags { count: SourceTagged::source_count, next: SourceTagged::next_tagged }
+    }
+}
+
+// The function pointers can be copied whatever the iterator, which `derive` would require to be `Clone`.
+impl<I, Item> Clone for Tags<I, Item> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<I, Item> Copy for Tags<I, Item> {}
+
 impl<Iter1, Iter2, Item> BiAltern<Iter1, Iter2, Item>
 where
     Iter1: Iterator<Item = Item>,
@@ -115,11 +197,411 @@ where
 {
     /// Creates a new instance of an `BiAltern` iterator.
     pub fn new(iter1 : Iter1, iter2 : Iter2) -> BiAltern<Iter1, Iter2, Item> {
+        Self::with_ratio(iter1, iter2, 1, 1)
+    }
+
+    /// Creates a new instance of an `BiAltern` iterator, which yields `ratio1` elements of `iter1` each time it
+    /// yields `ratio2` elements of `iter2`.
+    ///
+    /// The elements are spread as evenly as possible, following a smooth weighted round-robin: with a ratio of 3 to 1,
+    /// the order is `1 1 2 1 1 1 2 1 ...`. When an iterator is exhausted, the other one is traversed until its end.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `ratio1` or `ratio2` is 0.
+    ///
+    /// # Example
+    /// ```
+    /// use combin_iterator::altern::BiAltern;
+    /// let high = vec![1, 1, 1, 1, 1, 1, 1];
+    /// let low = vec![2, 2, 2];
+    ///
+    /// let iter = BiAltern::with_ratio(high.iter(), low.iter(), 3, 1);
+    /// assert_eq!(iter.collect::<Vec<_>>(), vec![&1, &1, &2, &1, &1, &1, &2, &1, &1, &2]);
+    /// ```
+    pub fn with_ratio(iter1 : Iter1, iter2 : Iter2, ratio1 : usize, ratio2 : usize) -> BiAltern<Iter1, Iter2, Item> {
+        assert!(ratio1 > 0 && ratio2 > 0, "the ratio of an iterator must be at least 1");
+        let divisor = gcd(ratio1, ratio2);
+        let ratio = (ratio1 / divisor, ratio2 / divisor);
         BiAltern {
             iter1: Some(iter1),
             iter2: Some(iter2),
-            next_is_first: true,
+            sources: (1, 1),
+            tags: (Tags::single(), Tags::single()),
+            next_is_first: ratio.0 >= ratio.1,
+            ratio,
+            credit: 0,
+            chunk: (1, 1),
+            run: 1,
+            termination: Termination::Longest,
+            limit: None,
+            round: None,
+            peeked: None,
+        }
+    }
+
+    /// Creates a new instance of an `BiAltern` iterator, which yields up to `chunk1` consecutive elements of `iter1`,
+    /// then up to `chunk2` consecutive elements of `iter2`, and so on.
+    ///
+    /// When an iterator has fewer elements left than its chunk, they are all yielded as a shorter chunk, and then the
+    /// other iterator is traversed until its end. With `Termination::Shortest`, the alternation stops after the shorter
+    /// chunk instead, and `with_complete_rounds` drops it, with the chunk of the other iterator in the same round.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `chunk1` or `chunk2` is 0.
+    ///
+    /// # Example
+    /// ```
+    /// use combin_iterator::altern::BiAltern;
+    /// let header = vec![1, 1, 1, 1, 1];
+    /// let body = vec![2, 2, 2, 2, 2, 2, 2];
+    ///
+    /// let iter = BiAltern::chunked(header.iter(), body.iter(), 2, 3);
+    /// assert_eq!(iter.collect::<Vec<_>>(), vec![&1, &1, &2, &2, &2, &1, &1, &2, &2, &2, &1, &2]);
+    /// ```
+    pub fn chunked(iter1 : Iter1, iter2 : Iter2, chunk1 : usize, chunk2 : usize) -> BiAltern<Iter1, Iter2, Item> {
+        assert!(chunk1 > 0 && chunk2 > 0, "the chunk of an iterator must be at least 1");
+        let mut bi_altern = Self::new(iter1, iter2);
+        bi_altern.chunk = (chunk1, chunk2);
+        bi_altern.run = chunk1;
+        bi_altern
+    }
+
+    /// Sets how the alternation ends when an iterator is exhausted.
+    ///
+    /// With `Termination::Shortest`, it stops as soon as it is the turn of an exhausted iterator, so that the elements
+    /// keep strictly alternating (the last element comes from the iterator which was not exhausted).
+    ///
+    /// # Example
+    /// ```
+    /// use combin_iterator::altern::{BiAltern, Termination};
+    /// let keys = vec!["a", "b", "c"];
+    /// let values = vec!["1", "2"];
+    ///
+    /// let iter = BiAltern::new(keys.iter(), values.iter()).with_termination(Termination::Shortest);
+    /// assert_eq!(iter.collect::<Vec<_>>(), vec![&"a", &"1", &"b", &"2", &"c"]);
+    /// ```
+    pub fn with_termination(mut self, termination : Termination) -> Self {
+        self.termination = termination;
+        self.limit = None;
+        self.round = None;
+        self
+    }
+
+    /// Stops the alternation like `Termination::Shortest`, but only at the end of a round: the elements of the last
+    /// round (`ratio1 + ratio2` elements, see `with_ratio`, or `chunk1 + chunk2`, see `chunked`) are dropped if an
+    /// iterator is exhausted before its end.
+    ///
+    /// The rounds start at the next element. If the size hints of the iterators are exact, like those of
+    /// `ExactSizeIterator`s, the number of elements yielded is computed from them. Otherwise, the elements of each
+    /// round are taken from the iterators when the round starts, and yielded once the round is complete.
+    ///
+    /// # Example
+    /// ```
+    /// use combin_iterator::altern::BiAltern;
+    /// let keys = vec!["a", "b", "c"];
+    /// let values = vec!["1", "2"];
+    ///
+    /// let iter = BiAltern::new(keys.iter(), values.iter()).with_complete_rounds();
+    /// assert_eq!(iter.collect::<Vec<_>>(), vec![&"a", &"1", &"b", &"2"]);
+    /// ```
+    pub fn with_complete_rounds(self) -> Self {
+        let mut bi_altern = self.with_termination(Termination::Shortest);
+        match bi_altern.size_hint() {
+            (lower, Some(upper)) if lower == upper => {
+                let (turns1, turns2) = bi_altern.turns();
+                let round = turns1 + turns2;
+                bi_altern.limit = Some(lower / round * round);
+            },
+            _ => bi_altern.round = Some(VecDeque::new()),
         }
+        bi_altern
+    }
+
+    /// Returns a reference to the next element, without consuming it.
+    ///
+    /// The element is taken from its iterator and kept for the next call to `next`. If the iterator whose turn it is
+    /// turns out to be exhausted, the element comes from the other one, like with `next`.
+    ///
+    /// # Example
+    /// ```
+    /// use combin_iterator::altern::BiAltern;
+    /// let vec1 = vec![1];
+    /// let vec2 = vec![2, 3, 4];
+    ///
+    /// let mut iter = BiAltern::new(vec1.iter(), vec2.iter());
+    /// assert_eq!(iter.next(), Some(&1));
+    /// assert_eq!(iter.next(), Some(&2));
+    /// // The first iterator is exhausted, so the next element comes from the second one.
+    /// assert_eq!(iter.peek(), Some(&&3));
+    /// assert_eq!(iter.collect::<Vec<_>>(), vec![&3, &4]);
+    /// ```
+    pub fn peek(&mut self) -> Option<&Item> {
+        self.fill_peeked().map(|(_, value)| &*value)
+    }
+
+    /// Returns the next element if `func` returns `true` for it, and keeps it for the next call otherwise.
+    ///
+    /// # Example
+    /// ```
+    /// use combin_iterator::altern::BiAltern;
+    /// let mut iter = BiAltern::new(1..4, 10..13);
+    /// let small = std::iter::from_fn(|| iter.next_if(|value| *value < 11)).collect::<Vec<_>>();
+    /// assert_eq!(small, vec![1, 10, 2]);
+    /// assert_eq!(iter.next(), Some(11));
+    /// ```
+    pub fn next_if(&mut self, func : impl FnOnce(&Item) -> bool) -> Option<Item> {
+        let next = match self.peeked.take() {
+            Some(peeked) => peeked,
+            None => self.next_source(),
+        };
+        match next {
+            Some((_, value)) if func(&value) => Some(value),
+            next => {
+                self.peeked = Some(next);
+                None
+            },
+        }
+    }
+
+    /// Takes the next element with `peek`, if it was not already.
+    fn fill_peeked(&mut self) -> Option<&mut (usize, Item)> {
+        if self.peeked.is_none() {
+            self.peeked = Some(self.next_source());
+        }
+        self.peeked.as_mut().and_then(Option::as_mut)
+    }
+
+    /// Returns the next element of the round in progress, tagged with its source, taking the next round from the
+    /// iterators once it is over, and dropping it if an iterator is exhausted before its end.
+    fn next_buffered(&mut self) -> Option<(usize, Item)> {
+        let mut round = self.round.take()?;
+        if round.is_empty() {
+            let (turns1, turns2) = self.turns();
+            for _ in 0..turns1 + turns2 {
+                match self.visit_source() {
+                    Some(next) => round.push_back(next),
+                    None => {
+                        round.clear();
+                        break;
+                    },
+                }
+            }
+        }
+        let next = round.pop_front();
+        self.round = Some(round);
+        next
+    }
+
+    /// Takes the peeked element: `Some(None)` if `peek` found the end of the alternation.
+    fn take_peeked(&mut self) -> Option<Option<Item>> {
+        self.peeked.take().map(|peeked| peeked.map(|(_, value)| value))
+    }
+
+    /// Returns the credit, which iterator to visit and how many visits are left in its chunk after a visit of the first
+    /// (if `first`) or of the second iterator, with `run` visits left in its chunk, for a given ratio and chunks.
+    ///
+    /// Each chunk, the first iterator earns `ratio.0` and the second one `ratio.1`, and the visited one pays
+    /// `ratio.0 + ratio.1`. The richest is visited next (the first one if both are as rich). `credit` is what
+    /// the first one owns, the second one owning its opposite.
+    fn schedule((credit, first, run) : (isize, bool, usize), ratio : (usize, usize), chunk : (usize, usize)) -> (isize, bool, usize) {
+        if run > 1 {
+            return (credit, first, run - 1);
+        }
+        let (ratio1, ratio2) = (ratio.0 as isize, ratio.1 as isize);
+        let credit = credit + ratio1 - if first { ratio1 + ratio2 } else { 0 };
+        let first = 2 * credit + ratio1 - ratio2 >= 0;
+        (credit, first, if first { chunk.0 } else { chunk.1 })
+    }
+
+    /// Moves to the iterator to visit after this one.
+    fn advance(&mut self) {
+        (self.credit, self.next_is_first, self.run) = Self::schedule((self.credit, self.next_is_first, self.run), self.ratio, self.chunk);
+    }
+
+    /// Returns the number of visits of the first and of the second iterator in a period.
+    fn turns(&self) -> (usize, usize) {
+        (self.ratio.0 * self.chunk.0, self.ratio.1 * self.chunk.1)
+    }
+
+    /// Returns, for each of the next visits of a period, whether it visits the first iterator.
+    fn period(&self) -> impl Iterator<Item = bool> {
+        let (ratio, chunk) = (self.ratio, self.chunk);
+        let (turns1, turns2) = self.turns();
+        let mut state = (self.credit, self.next_is_first, self.run);
+        (0..turns1 + turns2).map(move |_| {
+            let visit = state.1;
+            state = Self::schedule(state, ratio, chunk);
+            visit
+        })
+    }
+
+    /// Returns the position, among the next visits, of the visit of the first (if `first`) or of the second iterator
+    /// which comes after `visits` visits of it, or `None` if it overflows.
+    fn visit_position(&self, first : bool, visits : usize) -> Option<usize> {
+        let (turns1, turns2) = self.turns();
+        let turns = if first { turns1 } else { turns2 };
+        let (place, _) = self.period().enumerate().filter(|&(_, visit)| visit == first).nth(visits % turns)?;
+        (visits / turns).checked_mul(turns1 + turns2)?.checked_add(place)
+    }
+
+    /// Returns how many of the next `count` visits are visits of the first (if `first`) or of the second iterator.
+    fn visits(&self, first : bool, count : usize) -> usize {
+        let (turns1, turns2) = self.turns();
+        let turns = if first { turns1 } else { turns2 };
+        let period = turns1 + turns2;
+        count / period * turns + self.period().take(count % period).filter(|&visit| visit == first).count()
+    }
+
+    /// Returns the number of elements yielded with `Termination::Shortest` by iterators of `len1` and `len2`
+    /// elements (`None` for infinite iterators), or `None` if it is infinite.
+    fn shortest_len(&self, len1 : Option<usize>, len2 : Option<usize>) -> Option<usize> {
+        let position1 = len1.and_then(|len1| self.visit_position(true, len1));
+        let position2 = len2.and_then(|len2| self.visit_position(false, len2));
+        match (position1, position2) {
+            (Some(position1), Some(position2)) => Some(position1.min(position2)),
+            (position1, position2) => position1.or(position2),
+        }
+    }
+
+    /// Alternates between `next1` on the first iterator and `next2` on the second one.
+    ///
+    /// Once an iterator returned `None`, it is dropped and never called again.
+    fn next_with<T>(&mut self, next1: impl FnMut(&mut Iter1) -> Option<T>, next2: impl FnMut(&mut Iter2) -> Option<T>) -> Option<T> {
+        let next = match self.termination {
+            _ if self.limit == Some(0) => None,
+            Termination::Longest => self.next_longest(next1, next2),
+            Termination::Shortest if self.both_running() => {
+                let first = self.next_is_first;
+                self.advance();
+                let next = if first { visit(&mut self.iter1, next1) } else { visit(&mut self.iter2, next2) };
+                if next.is_none() {
+                    self.iter1 = None;
+                    self.iter2 = None;
+                }
+                next
+            },
+            Termination::Shortest => None,
+        };
+        if let (Some(_), Some(limit)) = (&next, self.limit.as_mut()) {
+            *limit -= 1;
+        }
+        next
+    }
+
+    /// Returns whether both iterators are running.
+    fn both_running(&self) -> bool {
+        self.iter1.is_some() && self.iter2.is_some()
+    }
+
+    /// Returns whether the iteration can be done in bulk: with `Termination::Longest`, and without a limit.
+    fn can_iterate_in_bulk(&self) -> bool {
+        self.termination == Termination::Longest && self.limit.is_none()
+    }
+
+    /// Returns the number of whole periods of visits (see `turns`) which can be done before an iterator is exhausted,
+    /// according to the lower bounds of their size hints.
+    fn safe_periods(&self) -> usize {
+        let (turns1, turns2) = self.turns();
+        match (self.iter1.as_ref(), self.iter2.as_ref()) {
+            (Some(iter1), Some(iter2)) => (iter1.size_hint().0 / turns1).min(iter2.size_hint().0 / turns2),
+            _ => 0,
+        }
+    }
+
+    /// Skips `periods` whole periods of visits, at most `safe_periods()`, with `nth` on each iterator.
+    ///
+    /// A whole period leaves the credit and the chunk unchanged, so the alternation goes on as if the elements were
+    /// yielded.
+    fn skip_periods(&mut self, periods : usize) {
+        let (turns1, turns2) = self.turns();
+        if let (Some(iter1), Some(iter2), true) = (self.iter1.as_mut(), self.iter2.as_mut(), periods > 0) {
+            iter1.nth(periods * turns1 - 1);
+            iter2.nth(periods * turns2 - 1);
+        }
+    }
+
+    /// Folds the elements with `Termination::Longest` while both iterators are running, without going through
+    /// `next`.
+    fn fold_alternating<B>(&mut self, init : B, mut f : impl FnMut(B, Item) -> B) -> B {
+        let mut acc = init;
+        while self.both_running() {
+            let first = self.next_is_first;
+            self.advance();
+            let next = if first { visit(&mut self.iter1, Iterator::next) } else { visit(&mut self.iter2, Iterator::next) };
+            if let Some(value) = next {
+                acc = f(acc, value);
+            }
+        }
+        acc
+    }
+
+    /// Alternates between `next1` and `next2` with `Termination::Longest`.
+    fn next_longest<T>(&mut self, mut next1: impl FnMut(&mut Iter1) -> Option<T>, mut next2: impl FnMut(&mut Iter2) -> Option<T>) -> Option<T> {
+        if self.both_running() {
+            let first = self.next_is_first;
+            self.advance();
+            if !first {
+                return visit(&mut self.iter2, &mut next2).or_else(|| visit(&mut self.iter1, next1));
+            }
+        }
+        visit(&mut self.iter1, &mut next1).or_else(|| visit(&mut self.iter2, next2))
+    }
+}
+
+impl<Iter1, Iter2, Item> BiAltern<Iter1, Iter2, Item>
+where
+    Iter1: DoubleEndedIterator<Item = Item> + ExactSizeIterator,
+    Iter2: DoubleEndedIterator<Item = Item> + ExactSizeIterator
+{
+    /// Drops from the back of the iterators the elements which will not be yielded with `Termination::Shortest`.
+    fn trim(&mut self) {
+        let (Some(iter1), Some(iter2)) = (self.iter1.as_ref(), self.iter2.as_ref()) else { return };
+        let (len1, len2) = (iter1.len(), iter2.len());
+        let len = match (self.shortest_len(Some(len1), Some(len2)), self.limit) {
+            (Some(len), Some(limit)) => Some(len.min(limit)),
+            (len, limit) => len.or(limit),
+        };
+        if let Some(len) = len {
+            let (visits1, visits2) = (self.visits(true, len), self.visits(false, len));
+            if let (Some(iter1), true) = (self.iter1.as_mut(), len1 > visits1) {
+                iter1.nth_back(len1 - visits1 - 1);
+            }
+            if let (Some(iter2), true) = (self.iter2.as_mut(), len2 > visits2) {
+                iter2.nth_back(len2 - visits2 - 1);
+            }
+        }
+    }
+
+    /// Returns the last element, ignoring the peeked element.
+    fn next_back_unpeeked(&mut self) -> Option<Item> {
+        if self.limit == Some(0) {
+            return None;
+        }
+        if self.termination == Termination::Shortest {
+            if !self.both_running() {
+                return None;
+            }
+            self.trim();
+        }
+
+        let first = match (self.iter1.as_ref(), self.iter2.as_ref()) {
+            (None, None) => return None,
+            (None, Some(_)) => false,
+            (Some(_), None) => true,
+            (Some(iter1), Some(iter2)) => {
+                // The last element is the one visited last, among the last element of each iterator.
+                let (len1, len2) = (iter1.len(), iter2.len());
+                len1 != 0 && (len2 == 0 || self.visit_position(true, len1 - 1).unwrap_or(usize::MAX)
+                    > self.visit_position(false, len2 - 1).unwrap_or(usize::MAX))
+            },
+        };
+        let next = if first { self.iter1.as_mut()?.next_back() } else { self.iter2.as_mut()?.next_back() };
+        if let (Some(_), Some(limit)) = (&next, self.limit.as_mut()) {
+            *limit -= 1;
+        }
+        next
     }
 }
 
@@ -128,26 +610,135 @@ impl<Iter1: Iterator<Item = Item>, Iter2: Iterator<Item = Item>, Item> Iterator
     type Item = Item;
 
     fn next(&mut self) -> Option<Self::Item> {
-        match (self.next_is_first, self.iter1.as_mut(), self.iter2.as_mut()) {
-            (true, Some(iter1), Some( iter2)) => {
-                self.next_is_first = !self.next_is_first;
-                iter1.next().or_else(|| {self.iter1 = None; iter2.next()})
-            },
-            (false, Some(iter1), Some( iter2)) => {
-                self.next_is_first = !self.next_is_first;
-                iter2.next().or_else(|| {self.iter2 = None; iter1.next()})
-            },
-            (true, Some(iter1), None) => iter1.next(),
-            (false, Some(iter1), None) => iter1.next(),
-            (true, None, Some(iter2)) => iter2.next(),
-            (false, None, Some(iter2)) => iter2.next(),
-            (true, None, None) => None,
-            (false, None, None) => None,
+        match self.take_peeked() {
+            Some(peeked) => peeked,
+            None if self.round.is_some() => self.next_buffered().map(|(_, value)| value),
+            None => self.next_with(Iterator::next, Iterator::next),
         }
     }
 
+    // `try_fold` keeps its default implementation, based on `next`: it cannot be overridden on stable Rust,
+    // because its signature uses the unstable `Try` trait.
+
+    /// Folds the elements, alternating without going through `next`, then folding the remaining iterator with
+    /// its own `fold` once the other one is exhausted.
+    fn fold<B, F>(mut self, init : B, mut f : F) -> B
+    where
+        F: FnMut(B, Self::Item) -> B
+    {
+        let init = match self.take_peeked() {
+            Some(Some(value)) => f(init, value),
+            Some(None) => return init,
+            None => init,
+        };
+        if !self.can_iterate_in_bulk() {
+            let mut acc = init;
+            for value in self.by_ref() {
+                acc = f(acc, value);
+            }
+            return acc;
+        }
+
+        let acc = self.fold_alternating(init, &mut f);
+        match (self.iter1.take(), self.iter2.take()) {
+            (Some(iter1), _) => iter1.fold(acc, f),
+            (_, Some(iter2)) => iter2.fold(acc, f),
+            _ => acc,
+        }
+    }
+
+    /// Returns the `n`th element, first skipping whole periods with `nth` on both iterators as long as their size
+    /// hints guarantee that they are not exhausted, which takes a constant time for iterators like ranges or slices.
+    fn nth(&mut self, mut n : usize) -> Option<Self::Item> {
+        match self.take_peeked() {
+            Some(Some(value)) if n == 0 => return Some(value),
+            Some(Some(_)) => n -= 1,
+            Some(None) => return None,
+            None => (),
+        }
+        if !self.can_iterate_in_bulk() {
+            for _ in 0..n {
+                self.next()?;
+            }
+            return self.next();
+        }
+
+        if self.both_running() {
+            let (turns1, turns2) = self.turns();
+            let period = turns1 + turns2;
+            let periods = (n / period).min(self.safe_periods());
+            self.skip_periods(periods);
+            n -= periods * period;
+        }
+        while self.both_running() {
+            if n == 0 {
+                return self.next();
+            }
+            self.next()?;
+            n -= 1;
+        }
+        visit(&mut self.iter1, |iter1| iter1.nth(n)).or_else(|| visit(&mut self.iter2, |iter2| iter2.nth(n)))
+    }
+
+    /// Counts the elements, skipping whole periods like `nth`, then counting the remaining iterator with its own
+    /// `count` once the other one is exhausted.
+    fn count(mut self) -> usize {
+        let mut count = match self.take_peeked() {
+            Some(Some(_)) => 1,
+            Some(None) => return 0,
+            None => 0,
+        };
+        if !self.can_iterate_in_bulk() {
+            return self.fold(count, |count, _| count + 1);
+        }
+
+        if self.both_running() {
+            let periods = self.safe_periods();
+            self.skip_periods(periods);
+            let (turns1, turns2) = self.turns();
+            count += periods * (turns1 + turns2);
+        }
+        let count = self.fold_alternating(count, |count, _| count + 1);
+        count + match (self.iter1.take(), self.iter2.take()) {
+            (Some(iter1), _) => iter1.count(),
+            (_, Some(iter2)) => iter2.count(),
+            _ => 0,
+        }
+    }
+
+    /// Returns the last element, skipping whole periods like `nth`, then taking the last element of the remaining
+    /// iterator with its own `last` once the other one is exhausted.
+    fn last(mut self) -> Option<Self::Item> {
+        let peeked = match self.take_peeked() {
+            Some(Some(value)) => Some(value),
+            Some(None) => return None,
+            None => None,
+        };
+        if !self.can_iterate_in_bulk() {
+            return self.fold(peeked, |_, value| Some(value));
+        }
+
+        if self.both_running() {
+            // Keep a period, so that the last element is not skipped.
+            self.skip_periods(self.safe_periods().saturating_sub(1));
+        }
+        let last = self.fold_alternating(None, |_, value| Some(value));
+        let rest = match (self.iter1.take(), self.iter2.take()) {
+            (Some(iter1), _) => iter1.last(),
+            (_, Some(iter2)) => iter2.last(),
+            _ => None,
+        };
+        rest.or(last).or(peeked)
+    }
+
     fn size_hint(&self) -> (usize, Option<usize>) {
-        match (self.iter1.as_ref(), self.iter2.as_ref()) {
+        let hint = match (self.iter1.as_ref(), self.iter2.as_ref()) {
+            (Some(iter1), Some(iter2)) if self.termination == Termination::Shortest => {
+                let (hint1, hint2) = (iter1.size_hint(), iter2.size_hint());
+                let lower = self.shortest_len(Some(hint1.0), Some(hint2.0)).unwrap_or(usize::MAX);
+                (lower, self.shortest_len(hint1.1, hint2.1))
+            },
+            _ if self.termination == Termination::Shortest => (0, Some(0)),
             (None, None) => (0, Some(0)),
             (None, Some(iter2)) => iter2.size_hint(),
             (Some(iter1), None) => iter1.size_hint(),
@@ -158,10 +749,25 @@ impl<Iter1: Iterator<Item = Item>, Iter2: Iterator<Item = Item>, Item> Iterator
                     (None, None) => None,
                     (None, Some(_)) => None,
                     (Some(_), None) => None,
-                    (Some(u1), Some(u2)) => Some(u1 + u2),
+                    (Some(u1), Some(u2)) => u1.checked_add(u2),
                 };
-                (hint1.0 + hint2.0, upper_bound)
+                (hint1.0.saturating_add(hint2.0), upper_bound)
             },
+        };
+        let hint = match self.round.as_ref() {
+            Some(round) => {
+                let (turns1, turns2) = self.turns();
+                complete_rounds_hint(hint, turns1 + turns2, round.len())
+            },
+            None => hint,
+        };
+        match self.peeked {
+            Some(Some(_)) => {
+                let (lower, upper) = limit_hint(hint, self.limit);
+                (lower.saturating_add(1), upper.and_then(|upper| upper.checked_add(1)))
+            },
+            Some(None) => (0, Some(0)),
+            None => limit_hint(hint, self.limit),
         }
     }
 }
@@ -171,20 +777,106 @@ where
         Iter1 : DoubleEndedIterator<Item = Item> + ExactSizeIterator,
         Iter2 : DoubleEndedIterator<Item = Item> + ExactSizeIterator,
 {
+    /// Returns the last element, which is the peeked element once the iterators are exhausted.
     fn next_back(&mut self) -> Option<Self::Item> {
-        match (self.iter1.as_mut(), self.iter2.as_mut()) {
-            (None, None) => None,
-            (None, Some(iter2)) => iter2.next_back(),
-            (Some(iter1), None) => iter1.next_back(),
-            (Some(iter1), Some(iter2)) => {
-                let n1 = iter1.len() + self.next_is_first.then(|| 0).unwrap_or_else(|| 1);
-                let n2 = iter2.len();
-                if n2 >= n1 {
-                    iter2.next_back()
-                } else {
-                    iter1.next_back()
-                }
-            },
+        match self.peeked {
+            Some(None) => None,
+            _ => self.next_back_unpeeked().or_else(|| self.take_peeked().flatten()),
+        }
+    }
+}
+
+impl<Iter1, Iter2, Item> BiAltern<Iter1, Iter2, Item>
+where
+    Iter1: SourceTagged<Item = Item>,
+    Iter2: SourceTagged<Item = Item>,
+{
+    /// Tags the elements with the sources of the iterators, instead of `0` for the first iterator and `1` for the
+    /// second one: the sources of the second iterator follow those of the first one (see `next_tagged`).
+    ///
+    /// It flattens a tree of nested `BiAltern`, whose other iterators can be wrapped into a `Source`. The elements
+    /// already taken by `peek` keep their source.
+    ///
+    /// # Example
+    /// ```
+    /// use combin_iterator::altern::{BiAltern, Source, SourceTagged};
+    /// let iter = BiAltern::new(BiAltern::new(0..2, 10..12), Source::new(20..22));
+    /// assert_eq!(iter.clone().enumerate_sources().collect::<Vec<_>>(), vec![(0, 0), (1, 20), (0, 10), (1, 21), (0, 1), (0, 11)]);
+    ///
+    /// let iter = iter.with_nested_sources();
+    /// assert_eq!(iter.enumerate_sources().collect::<Vec<_>>(), vec![(0, 0), (2, 20), (1, 10), (2, 21), (0, 1), (1, 11)]);
+    /// ```
+    pub fn with_nested_sources(mut self) -> Self {
+        self.tags = (Tags::nested(), Tags::nested());
+        self
+    }
+}
+
+impl<Iter1, Iter2, Item> BiAltern<Iter1, Iter2, Item>
+where
+    Iter1: Iterator<Item = Item>,
+    Iter2: Iterator<Item = Item>,
+{
+    /// Returns the number of sources of each iterator, or the last known one once it is dropped.
+    fn source_counts(&self) -> (usize, usize) {
+        (
+            self.iter1.as_ref().map_or(self.sources.0, self.tags.0.count),
+            self.iter2.as_ref().map_or(self.sources.1, self.tags.1.count),
+        )
+    }
+
+    /// Returns from which source the next element comes, without consuming it (see `peek`): `0` for the first
+    /// iterator and `1` for the second one, or with `with_nested_sources`, the index of its source in the tree, like
+    /// `next_tagged`.
+    ///
+    /// # Example
+    /// ```
+    /// use combin_iterator::altern::BiAltern;
+    /// let mut iter = BiAltern::new(0..1, 10..13);
+    /// assert_eq!(iter.peek_source(), Some(0));
+    /// assert_eq!(iter.next(), Some(0));
+    /// assert_eq!(iter.next(), Some(10));
+    /// // The first iterator is exhausted, so the next element comes from the second one.
+    /// assert_eq!(iter.peek_source(), Some(1));
+    /// ```
+    pub fn peek_source(&mut self) -> Option<usize> {
+        self.fill_peeked().map(|(source, _)| *source)
+    }
+
+    /// Returns the next element, tagged with its source, ignoring the peeked element.
+    fn next_source(&mut self) -> Option<(usize, Item)> {
+        match self.round.is_some() {
+            true => self.next_buffered(),
+            false => self.visit_source(),
+        }
+    }
+
+    /// Returns the next element from the iterators, tagged with its source.
+    fn visit_source(&mut self) -> Option<(usize, Item)> {
+        self.sources = self.source_counts();
+        let (offset, tags) = (self.sources.0, self.tags);
+        self.next_with(tags.0.next, |iter2| (tags.1.next)(iter2).map(|(source, value)| (offset + source, value)))
+    }
+}
+
+impl<Iter1, Iter2, Item> SourceTagged for BiAltern<Iter1, Iter2, Item>
+where
+    Iter1: Iterator<Item = Item>,
+    Iter2: Iterator<Item = Item>,
+{
+    /// Returns the number of sources of both iterators.
+    fn source_count(&self) -> usize {
+        let (sources1, sources2) = self.source_counts();
+        sources1 + sources2
+    }
+
+    /// Returns the next element, tagged with `0` if it comes from the first iterator and `1` otherwise, or with
+    /// `with_nested_sources`, with the index of its source in the first iterator, or in the second iterator shifted
+    /// by the number of sources of the first one.
+    fn next_tagged(&mut self) -> Option<(usize, Item)> {
+        match self.peeked.take() {
+            Some(peeked) => peeked,
+            None => self.next_source(),
         }
     }
 }
@@ -193,11 +885,42 @@ impl<Iter1: Iterator<Item = Item>, Iter2: Iterator<Item = Item>, Item> ExactSize
 where Iter1 : ExactSizeIterator,
       Iter2 : ExactSizeIterator {}
 
+/// An iterator is dropped as soon as it returns `None`, so `BiAltern` is fused even if its iterators are not.
+impl<Iter1: Iterator<Item = Item>, Iter2: Iterator<Item = Item>, Item> FusedIterator for BiAltern<Iter1, Iter2, Item> {}
+
+impl<Iter1: Iterator<Item = Item>, Iter2: Iterator<Item = Item>, Item> fmt::Debug for BiAltern<Iter1, Iter2, Item>
+{
+    /// Shows the running iterators (`0` for the first one, `1` for the second one), the one visited next, and the
+    /// settings of the alternation, without requiring the iterators to implement `Debug`.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (live1, live2) = (self.iter1.is_some(), self.iter2.is_some());
+        let sources = [live1, live2].into_iter().enumerate().filter(|(_, live)| *live).map(|(side, _)| side);
+        let next = match (live1, live2) {
+            (true, true) => Some(usize::from(!self.next_is_first)),
+            (true, false) => Some(0),
+            (false, true) => Some(1),
+            (false, false) => None,
+        };
+        f.debug_struct("BiAltern")
+            .field("sources", &sources.collect::<Vec<_>>())
+            .field("next", &next)
+            .field("ratio", &self.ratio)
+            .field("chunk", &self.chunk)
+            .field("termination", &self.termination)
+            .field("limit", &self.limit)
+            .field("peeked", &self.peeked.as_ref().and_then(|peeked| peeked.as_ref().map(|(source, _)| *source)))
+            .finish()
+    }
+}
+
 #[cfg(test)]
+// The original tests build their iterators from vectors.
+#[allow(clippy::useless_vec)]
 mod tests {
     use std::vec;
 
     use super::BiAltern;
+    use crate::altern::Termination;
 
     #[test]
     fn normal_usage() {
@@ -242,4 +965,414 @@ mod tests {
         let iter = BiAltern::new(vec1.iter(), vec2.iter());
         assert_eq!(iter.len(), 6);
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn ratio() {
+        let iter = BiAltern::with_ratio(std::iter::repeat_n('a', 9), std::iter::repeat_n('b', 3), 3, 1);
+        assert_eq!(iter.collect::<String>(), "aabaaabaaaba");
+
+        let iter = BiAltern::with_ratio(std::iter::repeat_n('a', 4), std::iter::repeat_n('b', 7), 4, 6);
+        assert_eq!(iter.collect::<String>(), "bababbababb");
+
+        let iter = BiAltern::with_ratio(std::iter::repeat_n('a', 2), std::iter::repeat_n('b', 2), 2, 2);
+        assert_eq!(iter.collect::<String>(), "abab");
+    }
+
+    #[test]
+    fn ratio_reverse_mirrors_forward() {
+        for (ratio1, ratio2) in [(1, 1), (3, 1), (1, 3), (2, 5), (4, 4)] {
+            for len1 in 0..8 {
+                for len2 in 0..8 {
+                    let iter = || BiAltern::with_ratio((0..len1).map(|i| (1, i)), (0..len2).map(|i| (2, i)), ratio1, ratio2);
+                    let mut forward = iter().collect::<Vec<_>>();
+                    forward.reverse();
+                    assert_eq!(iter().rev().collect::<Vec<_>>(), forward);
+
+                    let mut iter = iter();
+                    let first = iter.next();
+                    assert_eq!(iter.rev().chain(first).collect::<Vec<_>>(), forward);
+                }
+            }
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn zero_ratio() {
+        BiAltern::with_ratio(0..3, 0..3, 0, 1);
+    }
+
+    #[test]
+    fn chunked() {
+        let iter = BiAltern::chunked(std::iter::repeat_n('a', 7), std::iter::repeat_n('b', 4), 3, 2);
+        assert_eq!(iter.collect::<String>(), "aaabbaaabba");
+
+        let iter = BiAltern::chunked(std::iter::repeat_n('a', 2), std::iter::repeat_n('b', 6), 3, 2);
+        assert_eq!(iter.collect::<String>(), "aabbbbbb");
+
+        let iter = BiAltern::chunked(std::iter::repeat_n('a', 7), std::iter::repeat_n('b', 4), 3, 2).with_termination(Termination::Shortest);
+        assert_eq!(iter.collect::<String>(), "aaabbaaabba");
+
+        let iter = BiAltern::chunked(std::iter::repeat_n('a', 7), std::iter::repeat_n('b', 4), 3, 2).with_complete_rounds();
+        assert_eq!(iter.collect::<String>(), "aaabbaaabb");
+    }
+
+    #[test]
+    #[should_panic]
+    fn zero_chunk() {
+        BiAltern::chunked(0..3, 0..3, 2, 0);
+    }
+
+    #[test]
+    fn chunked_matches_forward() {
+        for (chunk1, chunk2) in [(1, 1), (3, 1), (2, 3)] {
+            for len1 in 0..8 {
+                for len2 in 0..8 {
+                    let longest = || BiAltern::chunked(0..len1, 10..10 + len2, chunk1, chunk2);
+                    let shortest = || longest().with_termination(Termination::Shortest);
+                    let rounds = || longest().with_complete_rounds();
+                    for iter in [&longest as &dyn Fn() -> _, &shortest, &rounds] {
+                        let expected = iter().collect::<Vec<_>>();
+                        assert_eq!(iter().len(), expected.len());
+                        assert_eq!(iter().count(), expected.len());
+                        assert_eq!(iter().last(), expected.last().copied());
+                        assert_eq!(iter().rev().collect::<Vec<_>>(), expected.iter().rev().copied().collect::<Vec<_>>());
+                        for n in 0..expected.len() + 2 {
+                            let mut nth = iter();
+                            assert_eq!(nth.nth(n), expected.get(n).copied());
+                            assert_eq!(nth.rev().collect::<Vec<_>>(), expected.get(n + 1..).unwrap_or_default().iter().rev().copied().collect::<Vec<_>>());
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn shortest() {
+        let iter = BiAltern::new(0..3, 10..12).with_termination(Termination::Shortest);
+        assert_eq!(iter.collect::<Vec<_>>(), vec![0, 10, 1, 11, 2]);
+
+        let iter = BiAltern::new(0..2, 10..12).with_termination(Termination::Shortest);
+        assert_eq!(iter.collect::<Vec<_>>(), vec![0, 10, 1, 11]);
+
+        let mut iter = BiAltern::new(0..1, 10..12).with_termination(Termination::Shortest);
+        assert_eq!(iter.len(), 2);
+        assert_eq!(iter.by_ref().collect::<Vec<_>>(), vec![0, 10]);
+        assert_eq!(iter.next(), None);
+
+        let iter = BiAltern::with_ratio(0..10, 10..12, 3, 1).with_termination(Termination::Shortest);
+        assert_eq!(iter.collect::<Vec<_>>(), vec![0, 1, 10, 2, 3, 4, 11, 5, 6, 7]);
+    }
+
+    #[test]
+    fn shortest_does_not_call_exhausted_again() {
+        let values = vec![1, 2, 3];
+        let mut calls = 0;
+        let keys = std::iter::from_fn(|| { calls += 1; None });
+        let iter = BiAltern::new(keys, values.into_iter()).with_termination(Termination::Shortest);
+        assert_eq!(iter.fuse().count(), 0);
+        assert_eq!(calls, 1);
+    }
+
+    #[test]
+    fn complete_rounds() {
+        let iter = BiAltern::new(0..3, 10..12).with_complete_rounds();
+        assert_eq!(iter.collect::<Vec<_>>(), vec![0, 10, 1, 11]);
+
+        let iter = BiAltern::with_ratio(0..10, 10..12, 3, 1).with_complete_rounds();
+        assert_eq!(iter.len(), 8);
+        assert_eq!(iter.collect::<Vec<_>>(), vec![0, 1, 10, 2, 3, 4, 11, 5]);
+
+        let iter = BiAltern::new(0..0, 10..12).with_complete_rounds();
+        assert_eq!(iter.len(), 0);
+    }
+
+    #[test]
+    fn complete_rounds_buffered() {
+        // The size hint of a filtered iterator is not exact, so each round is taken before it is yielded.
+        let mut iter = BiAltern::new((0..3).filter(|_| true), 10..12).with_complete_rounds();
+        assert_eq!(iter.size_hint(), (0, Some(4)));
+        assert_eq!(iter.next(), Some(0));
+        assert_eq!(iter.size_hint(), (1, Some(3)));
+        assert_eq!(iter.collect::<Vec<_>>(), vec![10, 1, 11]);
+
+        for (ratio1, ratio2) in [(1, 1), (3, 1), (2, 5)] {
+            for len1 in 0..8 {
+                for len2 in 0..8 {
+                    let exact = BiAltern::with_ratio(0..len1, 10..10 + len2, ratio1, ratio2).with_complete_rounds();
+                    let filtered = BiAltern::with_ratio((0..len1).filter(|_| true), 10..10 + len2, ratio1, ratio2).with_complete_rounds();
+                    assert_eq!(filtered.collect::<Vec<_>>(), exact.collect::<Vec<_>>());
+                }
+            }
+        }
+
+        use crate::altern::{Source, SourceTagged};
+        let iter = BiAltern::new(Source::new((0..3).filter(|_| true)), BiAltern::new(10..12, 20..22))
+            .with_nested_sources()
+            .with_complete_rounds();
+        assert_eq!(iter.enumerate_sources().collect::<Vec<_>>(), vec![(0, 0), (1, 10), (0, 1), (2, 20), (0, 2), (1, 11)]);
+    }
+
+    #[test]
+    fn shortest_reverse_mirrors_forward() {
+        for (ratio1, ratio2) in [(1, 1), (3, 1), (2, 5)] {
+            for len1 in 0..8 {
+                for len2 in 0..8 {
+                    let shortest = || BiAltern::with_ratio(0..len1, 10..10 + len2, ratio1, ratio2).with_termination(Termination::Shortest);
+                    let rounds = || BiAltern::with_ratio(0..len1, 10..10 + len2, ratio1, ratio2).with_complete_rounds();
+                    for iter in [&shortest as &dyn Fn() -> _, &rounds] {
+                        let mut forward = iter().collect::<Vec<_>>();
+                        assert_eq!(iter().len(), forward.len());
+                        forward.reverse();
+                        assert_eq!(iter().rev().collect::<Vec<_>>(), forward);
+
+                        let mut iter = iter();
+                        let first = iter.next();
+                        assert_eq!(iter.rev().chain(first).collect::<Vec<_>>(), forward);
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn internal_iteration() {
+        let lens = [0, 1, 2, 5, 9];
+        for (ratio1, ratio2) in [(1, 1), (3, 1), (2, 5)] {
+            for len1 in lens {
+                for len2 in lens {
+                    for filter in [false, true] {
+                        // Filtered iterators have a lower bound of 0, so nothing can be skipped.
+                        let iter = || BiAltern::with_ratio(
+                            (0..len1).map(|i| (1, i)).filter(move |_| !filter || len1 > 0),
+                            (0..len2).map(|i| (2, i)).filter(move |_| !filter || len2 > 0),
+                            ratio1, ratio2
+                        );
+                        let expected = iter().collect::<Vec<_>>();
+                        assert_eq!(iter().fold(vec![], |mut acc, value| { acc.push(value); acc }), expected);
+                        assert_eq!(iter().count(), expected.len());
+                        assert_eq!(iter().last(), expected.last().copied());
+                        for n in 0..expected.len() + 2 {
+                            let mut nth = iter();
+                            assert_eq!(nth.nth(n), expected.get(n).copied());
+                            assert_eq!(nth.collect::<Vec<_>>(), expected.get(n + 1..).unwrap_or_default());
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn internal_iteration_with_termination() {
+        let iter = || BiAltern::new(0..3, 10..12).with_termination(Termination::Shortest);
+        assert_eq!(iter().sum::<i32>(), 24);
+        assert_eq!(iter().count(), 5);
+        assert_eq!(iter().last(), Some(2));
+        assert_eq!(iter().nth(3), Some(11));
+
+        let iter = || BiAltern::new(0..3, 10..12).with_complete_rounds();
+        assert_eq!(iter().count(), 4);
+        assert_eq!(iter().last(), Some(11));
+    }
+
+    #[test]
+    fn nth_skips_in_bulk() {
+        let iter = BiAltern::new(BiAltern::new(0..usize::MAX / 4, 0..usize::MAX / 4), 0..usize::MAX / 4);
+        assert_eq!(iter.count(), usize::MAX / 4 * 3);
+
+        // The order is `1 2 1 1 2 1 ...`, so the element `3 * k` is the element `2 * k` of the first iterator.
+        let mut iter = BiAltern::with_ratio(0..usize::MAX, 0..usize::MAX, 2, 1);
+        assert_eq!(iter.nth(usize::MAX - 3), Some(usize::MAX / 3 * 2 - 2));
+    }
+
+    #[test]
+    fn size_hint_overflow() {
+        let iter = BiAltern::new(0..usize::MAX, 0..usize::MAX);
+        assert_eq!(iter.size_hint(), (usize::MAX, None));
+
+        let iter = BiAltern::new(0..usize::MAX, std::iter::empty());
+        assert_eq!(iter.size_hint(), (usize::MAX, Some(usize::MAX)));
+    }
+
+    #[test]
+    fn peek_skips_exhausted() {
+        use crate::altern::Source;
+        let mut iter = BiAltern::new(Source::new(0..1), Source::new(10..13));
+        assert_eq!(iter.peek(), Some(&0));
+        assert_eq!(iter.peek_source(), Some(0));
+        assert_eq!(iter.len(), 4);
+        assert_eq!(iter.next(), Some(0));
+        assert_eq!(iter.next_if(|value| *value == 11), None);
+        assert_eq!(iter.peek_source(), Some(1));
+        assert_eq!(iter.next_if(|value| *value == 10), Some(10));
+        assert_eq!(iter.peek_source(), Some(1));
+        assert_eq!(iter.len(), 2);
+        assert_eq!(iter.collect::<Vec<_>>(), vec![11, 12]);
+
+        let mut iter = BiAltern::new(Source::new(0..0), Source::new(0..0));
+        assert_eq!(iter.peek(), None);
+        assert_eq!(iter.peek_source(), None);
+        assert_eq!(iter.next_if(|_| true), None);
+        assert_eq!(iter.size_hint(), (0, Some(0)));
+        assert_eq!(iter.next(), None);
+    }
+
+    #[test]
+    fn peek_any_iterator() {
+        let mut iter = BiAltern::new((0..10).scan(0, |_, x| Some(x)), 10..12);
+        assert_eq!(iter.peek(), Some(&0));
+        assert_eq!(iter.next(), Some(0));
+        assert_eq!(iter.next_if(|value| *value == 10), Some(10));
+        assert_eq!(iter.next_if(|value| *value == 10), None);
+        assert_eq!(iter.by_ref().take(3).collect::<Vec<_>>(), vec![1, 11, 2]);
+    }
+
+    #[test]
+    fn peek_then_internal_iteration() {
+        let iter = || {
+            let mut iter = BiAltern::new(0..5, 10..12);
+            assert_eq!(iter.peek(), Some(&0));
+            iter
+        };
+        assert_eq!(iter().collect::<Vec<_>>(), vec![0, 10, 1, 11, 2, 3, 4]);
+        assert_eq!(iter().count(), 7);
+        assert_eq!(iter().last(), Some(4));
+        assert_eq!(iter().nth(1), Some(10));
+        assert_eq!(iter().nth(2), Some(1));
+        assert_eq!(iter().rev().collect::<Vec<_>>(), vec![4, 3, 2, 11, 1, 10, 0]);
+    }
+
+    #[test]
+    fn peek_with_termination() {
+        let mut iter = BiAltern::new(0..3, 10..11).with_termination(Termination::Shortest);
+        assert_eq!(iter.by_ref().take(3).collect::<Vec<_>>(), vec![0, 10, 1]);
+        assert_eq!(iter.peek(), None);
+        assert_eq!(iter.size_hint(), (0, Some(0)));
+        assert_eq!(iter.next(), None);
+
+        let mut iter = BiAltern::new(0..3, 10..12).with_complete_rounds();
+        assert_eq!(iter.peek(), Some(&0));
+        assert_eq!(iter.len(), 4);
+        assert_eq!(iter.next_back(), Some(11));
+        assert_eq!(iter.collect::<Vec<_>>(), vec![0, 10, 1]);
+    }
+
+    #[test]
+    fn peek_sources() {
+        use crate::altern::{Source, SourceTagged};
+        let mut iter = BiAltern::new(Source::new(0..2), BiAltern::new(10..12, 20..22)).with_nested_sources();
+        assert_eq!(iter.next_tagged(), Some((0, 0)));
+        assert_eq!(iter.peek_source(), Some(1));
+        assert_eq!(iter.next_tagged(), Some((1, 10)));
+        assert_eq!(iter.peek(), Some(&1));
+        assert_eq!(iter.enumerate_sources().collect::<Vec<_>>(), vec![(0, 1), (2, 20), (1, 11), (2, 21)]);
+
+        // The source of an element peeked in the second iterator is its source in the nested alternation.
+        let mut iter = BiAltern::new(Source::new(0..2), BiAltern::new(10..12, 20..22)).with_nested_sources();
+        assert_eq!(iter.by_ref().take(3).collect::<Vec<_>>(), vec![0, 10, 1]);
+        assert_eq!(iter.peek_source(), Some(2));
+        assert_eq!(iter.peek(), Some(&20));
+        assert_eq!(iter.next_tagged(), Some((2, 20)));
+        assert_eq!(iter.next(), Some(11));
+        assert_eq!(iter.peek_source(), Some(2));
+        assert_eq!(iter.enumerate_sources().collect::<Vec<_>>(), vec![(2, 21)]);
+
+        // An element taken by `peek` keeps its source in the nested alternation.
+        let mut iter = BiAltern::new(Source::new(0..1), BiAltern::new(10..12, 20..22)).with_nested_sources();
+        assert_eq!(iter.by_ref().take(2).collect::<Vec<_>>(), vec![0, 10]);
+        assert_eq!(iter.peek(), Some(&20));
+        assert_eq!(iter.peek_source(), Some(2));
+        assert_eq!(iter.next_tagged(), Some((2, 20)));
+        assert_eq!(iter.enumerate_sources().collect::<Vec<_>>(), vec![(1, 11), (2, 21)]);
+    }
+
+    #[test]
+    fn peek_then_next_tagged_nested() {
+        use crate::altern::{Source, SourceTagged};
+        let tree = || BiAltern::new(BiAltern::new(0..3, 10..13), Source::new(100..103)).with_nested_sources();
+        let mut iter = tree();
+        assert_eq!(iter.next_tagged(), Some((0, 0)));
+        assert_eq!(iter.next_tagged(), Some((2, 100)));
+        assert_eq!(iter.peek(), Some(&10));
+        assert_eq!(iter.next_tagged(), Some((1, 10)));
+
+        let mut iter = tree();
+        assert_eq!(iter.next_if(|value| *value == 1), None);
+        assert_eq!(iter.next_tagged(), Some((0, 0)));
+        assert_eq!(iter.next_if(|value| *value == 1), None);
+        assert_eq!(iter.next_if(|value| *value == 100), Some(100));
+        assert_eq!(iter.next_if(|value| *value == 11), None);
+        assert_eq!(iter.next_tagged(), Some((1, 10)));
+
+        // The elements of a buffered round keep their source too.
+        let iter = BiAltern::new(BiAltern::new((0..3).filter(|_| true), 10..13), Source::new(100..102))
+            .with_nested_sources()
+            .with_complete_rounds();
+        assert_eq!(iter.enumerate_sources().collect::<Vec<_>>(), vec![(0, 0), (2, 100), (1, 10), (2, 101)]);
+    }
+
+    #[test]
+    fn clone_replays() {
+        let mut iter = BiAltern::with_ratio(0..4, 10..12, 2, 1);
+        assert_eq!(iter.next(), Some(0));
+        assert_eq!(iter.peek(), Some(&10));
+        let copy = iter.clone();
+        assert_eq!(iter.collect::<Vec<_>>(), vec![10, 1, 2, 11, 3]);
+        assert_eq!(copy.collect::<Vec<_>>(), vec![10, 1, 2, 11, 3]);
+    }
+
+    #[test]
+    fn debug() {
+        use crate::altern::Source;
+        let mut iter = BiAltern::new(Source::new(0..1), Source::new(10..12));
+        assert_eq!(
+            format!("{iter:?}"),
+            "BiAltern { sources: [0, 1], next: Some(0), ratio: (1, 1), chunk: (1, 1), termination: Longest, limit: None, peeked: None }"
+        );
+        iter.next();
+        iter.next();
+        assert_eq!(iter.peek_source(), Some(1));
+        assert_eq!(
+            format!("{iter:?}"),
+            "BiAltern { sources: [1], next: Some(1), ratio: (1, 1), chunk: (1, 1), termination: Longest, limit: None, peeked: Some(1) }"
+        );
+    }
+
+    #[test]
+    fn drops_exhausted_iterators() {
+        use std::rc::Rc;
+        let shared = Rc::new(());
+        let (held1, held2) = (Rc::clone(&shared), Rc::clone(&shared));
+        let mut iter = BiAltern::new((0..1).inspect(move |_| { let _ = &held1; }), (10..13).inspect(move |_| { let _ = &held2; }));
+        assert_eq!(iter.by_ref().take(3).collect::<Vec<_>>(), vec![0, 10, 11]);
+        assert_eq!(Rc::strong_count(&shared), 2);
+        assert_eq!(iter.by_ref().count(), 1);
+        assert_eq!(Rc::strong_count(&shared), 1);
+    }
+
+    #[test]
+    fn fused() {
+        fn assert_fused<T: std::iter::FusedIterator>(_: &T) {}
+        let mut iter = BiAltern::new(0..1, vec![10].into_iter());
+        assert_fused(&iter);
+        assert_eq!(iter.by_ref().count(), 2);
+        assert_eq!(iter.next(), None);
+        assert_eq!(iter.next(), None);
+
+        // Iterators yielding again after `None` are dropped at their first `None`.
+        let flaky = |start: usize| {
+            let mut calls = start;
+            std::iter::from_fn(move || {
+                calls += 1;
+                (calls % 2 == 1).then_some(calls)
+            })
+        };
+        let mut iter = BiAltern::new(flaky(0), flaky(10));
+        assert_fused(&iter);
+        assert_eq!(iter.by_ref().collect::<Vec<_>>(), vec![1, 11]);
+        assert_eq!(iter.next(), None);
+        assert_eq!(iter.next(), None);
+    }
+}
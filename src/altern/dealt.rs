@@ -0,0 +1,407 @@
+//! # Dealt
+//!
+//! The `dealt` module provides the inverse of an alternation: `Unaltern::unaltern(n)` deals the elements of one
+//! iterator into `n` iterators, `Dealt`, like cards: the output `i` receives the elements `i`, `i + n`, `i + 2n`...
+//!
+//! ## Usage
+//!
+//! Call `unaltern(n)` on any iterator to get a `Vec` of `n` outputs. They share the iterator, which is only advanced
+//! when an output needs its next element. The elements taken on the way for the other outputs are buffered until
+//! these outputs consume them.
+//!
+//! ## Examples
+//!
+//! ```rust
+//! use combin_iterator::altern::{Unaltern, VecAltern};
+//! let mut outputs = (0..10).unaltern(3);
+//!
+//! assert_eq!(outputs[1].next(), Some(1));
+//! assert_eq!(outputs[2].by_ref().collect::<Vec<_>>(), vec![2, 5, 8]);
+//! assert_eq!(outputs[0].next(), Some(0));
+//!
+//! // Alternating between the outputs gives back the elements in the original order.
+//! let outputs = (0..10).unaltern(3);
+//! assert_eq!(VecAltern::from(outputs).collect::<Vec<_>>(), (0..10).collect::<Vec<_>>());
+//! ```
+//!
+//! ## Notes
+//!
+//! - Only the elements taken from the iterator but not yet consumed are buffered, so the buffers hold at most the
+//!   lag between the most advanced output and the others. A dropped output does not buffer anything anymore.
+//! - `unaltern_bounded(n, capacity)` bounds each buffer to `capacity` elements for `TryDealt::try_next`: it returns
+//!   `Err(Blocked)` when the next element of an output comes after an element for a full buffer, without taking
+//!   anything from the iterator, until that buffer is consumed. `next` still yields all the elements, buffering beyond
+//!   the capacity if needed (see `TryDealt`).
+//! - The outputs share the iterator through an `Rc`, so they cannot be sent to another thread.
+
+use std::cell::{Cell, RefCell};
+use std::collections::VecDeque;
+use std::error::Error;
+use std::fmt;
+use std::rc::Rc;
+
+/// Trait to deal the elements of an iterator into several iterators.
+/// Implemented on Iterator
+pub trait Unaltern: Iterator {
+    /// Deals the elements into `n` iterators: the iterator `i` yields the elements `i`, `i + n`, `i + 2n`...
+    ///
+    /// # Panics
+    ///
+    /// Panics if `n` is 0.
+    fn unaltern(self, n: usize) -> Vec<Dealt<Self>>
+    where
+        Self: Sized;
+
+    /// Deals the elements into `n` iterators like `unaltern`, whose `try_next` buffers at most `capacity` elements for
+    /// each of them.
+    ///
+    /// When the next element of an output comes after an element for an output whose buffer is full, `try_next`
+    /// returns `Err(Blocked)` without taking anything from the iterator, and yields the elements again once that
+    /// buffer is consumed. `next` never blocks, and buffers beyond the capacity instead.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `n` is 0.
+    ///
+    /// # Example
+    /// ```
+    /// use combin_iterator::altern::{Blocked, Unaltern};
+    /// let mut outputs = (0..10).unaltern_bounded(2, 1);
+    ///
+    /// assert_eq!(outputs[0].try_next(), Ok(Some(0)));
+    /// assert_eq!(outputs[0].try_next(), Ok(Some(2)));
+    /// // The buffer of the output 1 is full with 1.
+    /// assert_eq!(outputs[0].try_next(), Err(Blocked));
+    /// assert_eq!(outputs[1].next(), Some(1));
+    /// assert_eq!(outputs[0].try_next(), Ok(Some(4)));
+    /// ```
+    fn unaltern_bounded(self, n: usize, capacity: usize) -> Vec<TryDealt<Self>>
+    where
+        Self: Sized;
+}
+
+impl<I: Iterator> Unaltern for I {
+    fn unaltern(self, n: usize) -> Vec<Dealt<Self>>
+    where
+        Self: Sized
+    {
+        deal(self, n, None)
+    }
+
+    fn unaltern_bounded(self, n: usize, capacity: usize) -> Vec<TryDealt<Self>>
+    where
+        Self: Sized
+    {
+        deal(self, n, Some(capacity)).into_iter().map(|dealt| TryDealt { dealt }).collect()
+    }
+}
+
+/// Creates the `n` outputs sharing `iter`, with buffers of `capacity` elements if any.
+fn deal<I: Iterator>(iter: I, n: usize, capacity: Option<usize>) -> Vec<Dealt<I>> {
+    assert!(n > 0, "an iterator must be dealt into at least 1 iterator");
+    let shared = Rc::new(Shared {
+        dealer: RefCell::new(Dealer {
+            iter: Some(iter),
+            queues: (0..n).map(|_| VecDeque::new()).collect(),
+            next: 0,
+            capacity,
+        }),
+        dropped: (0..n).map(|_| Cell::new(false)).collect(),
+    });
+    (0..n).map(|output| Dealt { shared: shared.clone(), output }).collect()
+}
+
+/// The state shared by the outputs.
+///
+/// An output marks itself as dropped without borrowing the dealer, which may be borrowed when it is dropped (by
+/// an element holding it, for example), so that its elements are dropped instead of buffered in any case.
+struct Shared<I: Iterator> {
+    dealer: RefCell<Dealer<I>>,
+    dropped: Vec<Cell<bool>>,
+}
+
+/// The iterator shared by the outputs, and the elements taken from it for each output.
+struct Dealer<I: Iterator> {
+    iter: Option<I>,
+    queues: Vec<VecDeque<I::Item>>,
+    next: usize,
+    capacity: Option<usize>,
+}
+
+impl<I: Iterator> Dealer<I> {
+    /// Returns the next element of `output`, taking elements from the iterator until it gets one, or `None` if the
+    /// iterator is exhausted.
+    fn next(&mut self, output: usize, dropped: &[Cell<bool>]) -> Option<I::Item> {
+        if let Some(value) = self.queues[output].pop_front() {
+            return Some(value);
+        }
+        loop {
+            let Some(value) = self.iter.as_mut()?.next() else {
+                self.iter = None;
+                return None;
+            };
+            let receiver = self.next;
+            self.next = if receiver + 1 == self.queues.len() { 0 } else { receiver + 1 };
+            if receiver == output {
+                return Some(value);
+            }
+            if dropped[receiver].get() {
+                // The output may have been dropped while the dealer was borrowed, with elements left in its queue.
+                self.queues[receiver] = VecDeque::new();
+            } else {
+                self.queues[receiver].push_back(value);
+            }
+        }
+    }
+
+    /// Returns whether `output` has no buffered element, and its next element, if the size hint of the iterator
+    /// allows one, comes after an element for a full buffer.
+    fn is_blocked(&self, output: usize, dropped: &[Cell<bool>]) -> bool {
+        let Some(capacity) = self.capacity else { return false };
+        let n = self.queues.len();
+        let upper = self.iter.as_ref().map_or(Some(0), |iter| iter.size_hint().1);
+        self.queues[output].is_empty() && upper.is_none_or(|upper| self.share(output, upper) > 0) && (0..n)
+            .map(|offset| (self.next + offset) % n)
+            .take_while(|&receiver| receiver != output)
+            .any(|receiver| !dropped[receiver].get() && self.queues[receiver].len() >= capacity)
+    }
+
+    /// Returns the number of elements among the first `len` elements left in the iterator which go to `output`.
+    fn share(&self, output: usize, len: usize) -> usize {
+        let n = self.queues.len();
+        let offset = (output + n - self.next) % n;
+        if len > offset { (len - offset - 1) / n + 1 } else { 0 }
+    }
+}
+
+/// One of the iterators created by `Unaltern::unaltern`.
+pub struct Dealt<I: Iterator> {
+    shared: Rc<Shared<I>>,
+    output: usize,
+}
+
+impl<I: Iterator> Iterator for Dealt<I> {
+    type Item = I::Item;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.shared.dealer.borrow_mut().next(self.output, &self.shared.dropped)
+    }
+
+    /// Returns the number of buffered elements, plus the share of this output in the size hint of the iterator.
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let dealer = self.shared.dealer.borrow();
+        let buffered = dealer.queues[self.output].len();
+        let (lower, upper) = dealer.iter.as_ref().map_or((0, Some(0)), Iterator::size_hint);
+        (
+            buffered.saturating_add(dealer.share(self.output, lower)),
+            upper.and_then(|upper| buffered.checked_add(dealer.share(self.output, upper))),
+        )
+    }
+}
+
+impl<I: ExactSizeIterator> ExactSizeIterator for Dealt<I> {}
+
+impl<I: Iterator> Drop for Dealt<I> {
+    /// Drops the buffered elements, and the next ones of this output.
+    ///
+    /// If the dealer is borrowed, the buffered elements are dropped when it next deals an element to this output.
+    fn drop(&mut self) {
+        self.shared.dropped[self.output].set(true);
+        let queue = match self.shared.dealer.try_borrow_mut() {
+            Ok(mut dealer) => std::mem::take(&mut dealer.queues[self.output]),
+            Err(_) => VecDeque::new(),
+        };
+        // The elements are dropped once the dealer is released, in case they hold other outputs.
+        drop(queue);
+    }
+}
+
+/// One of the iterators created by `Unaltern::unaltern_bounded`.
+///
+/// Only `try_next` respects the capacity of the buffers. `next` behaves like the one of `Dealt`, and buffers beyond
+/// the capacity, so that the output can be used like any iterator, by a `for` loop or an alternation.
+pub struct TryDealt<I: Iterator> {
+    dealt: Dealt<I>,
+}
+
+impl<I: Iterator> TryDealt<I> {
+    /// Returns the next element like `next`, or `Err(Blocked)` if taking it would buffer an element for an output
+    /// whose buffer is full.
+    pub fn try_next(&mut self) -> Result<Option<I::Item>, Blocked> {
+        let Dealt { shared, output } = &self.dealt;
+        let mut dealer = shared.dealer.borrow_mut();
+        if dealer.is_blocked(*output, &shared.dropped) {
+            return Err(Blocked);
+        }
+        Ok(dealer.next(*output, &shared.dropped))
+    }
+}
+
+impl<I: Iterator> Iterator for TryDealt<I> {
+    type Item = I::Item;
+
+    /// Returns the next element, buffering the elements taken on the way for the other outputs even if their buffer
+    /// is full.
+    fn next(&mut self) -> Option<Self::Item> {
+        self.dealt.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.dealt.size_hint()
+    }
+}
+
+impl<I: ExactSizeIterator> ExactSizeIterator for TryDealt<I> {}
+
+/// The error returned by `TryDealt::try_next` when the next element of the output comes after an element for an
+/// output whose buffer is full.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Blocked;
+
+impl fmt::Display for Blocked {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("the buffer of another output is full")
+    }
+}
+
+impl Error for Blocked {}
+
+#[cfg(test)]
+mod tests {
+    use std::any::Any;
+    use std::cell::{Cell, RefCell};
+    use std::rc::Rc;
+
+    use super::{Blocked, Unaltern};
+    use crate::altern;
+    use crate::altern::VecAltern;
+
+    #[test]
+    fn deal() {
+        let outputs = (0..10).unaltern(3);
+        assert_eq!(outputs.into_iter().map(Iterator::collect::<Vec<_>>).collect::<Vec<_>>(), vec![vec![0, 3, 6, 9], vec![1, 4, 7], vec![2, 5, 8]]);
+
+        let mut outputs = (0..10).unaltern(1);
+        assert_eq!(outputs.pop().unwrap().collect::<Vec<_>>(), (0..10).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn round_trip() {
+        for n in 1..6 {
+            for len in 0..12 {
+                let outputs = (0..len).unaltern(n);
+                assert_eq!(VecAltern::from(outputs).collect::<Vec<_>>(), (0..len).collect::<Vec<_>>());
+            }
+        }
+    }
+
+    #[test]
+    fn interleaved_consumption() {
+        let mut outputs = (0..9).unaltern(3);
+        assert_eq!(outputs[2].next(), Some(2));
+        assert_eq!(outputs[0].next(), Some(0));
+        assert_eq!(outputs[2].next(), Some(5));
+        assert_eq!(outputs[1].by_ref().collect::<Vec<_>>(), vec![1, 4, 7]);
+        assert_eq!(outputs[0].by_ref().collect::<Vec<_>>(), vec![3, 6]);
+        assert_eq!(outputs[2].next(), Some(8));
+        assert_eq!(outputs[2].next(), None);
+    }
+
+    #[test]
+    fn len() {
+        let mut outputs = (0..10).unaltern(3);
+        assert_eq!(outputs.iter().map(ExactSizeIterator::len).collect::<Vec<_>>(), vec![4, 3, 3]);
+        outputs[1].next();
+        outputs[1].next();
+        assert_eq!(outputs.iter().map(ExactSizeIterator::len).collect::<Vec<_>>(), vec![4, 1, 3]);
+        outputs[0].by_ref().for_each(drop);
+        assert_eq!(outputs.iter().map(ExactSizeIterator::len).collect::<Vec<_>>(), vec![0, 1, 3]);
+    }
+
+    #[test]
+    fn dropped_output_is_not_buffered() {
+        let dropped = Cell::new(0);
+        struct Counted<'a>(&'a Cell<usize>);
+        impl Drop for Counted<'_> {
+            fn drop(&mut self) {
+                self.0.set(self.0.get() + 1);
+            }
+        }
+
+        let mut outputs = (0..10).map(|_| Counted(&dropped)).unaltern(2);
+        drop(outputs.remove(1));
+        assert_eq!(outputs[0].by_ref().count(), 5);
+        assert_eq!(dropped.get(), 10);
+    }
+
+    #[test]
+    fn output_dropped_while_dealing() {
+        let tracker = Rc::new(());
+        let slot: Rc<RefCell<Option<Box<dyn Any>>>> = Rc::default();
+        let (holder, values) = (slot.clone(), tracker.clone());
+        let mut count = 0;
+        let iter = std::iter::from_fn(move || {
+            count += 1;
+            if count == 3 {
+                holder.borrow_mut().take();
+            }
+            (count <= 6).then(|| values.clone())
+        });
+        let mut outputs = iter.unaltern(2);
+        *slot.borrow_mut() = Some(Box::new(outputs.pop().unwrap()));
+        let values = outputs[0].by_ref().collect::<Vec<_>>();
+        assert_eq!(values.len(), 3);
+        // Only the tracker and the values of the output 0 are left: nothing is buffered.
+        assert_eq!(Rc::strong_count(&tracker), 4);
+    }
+
+    #[test]
+    fn bounded() {
+        let mut outputs = (0..7).unaltern_bounded(3, 2);
+        assert_eq!((0..3).map(|_| outputs[0].try_next()).collect::<Vec<_>>(), vec![Ok(Some(0)), Ok(Some(3)), Ok(Some(6))]);
+        assert_eq!(outputs[0].try_next(), Ok(None));
+        assert_eq!(outputs[1].len(), 2);
+
+        let mut outputs = (0..10).unaltern_bounded(3, 1);
+        assert_eq!(outputs[2].try_next(), Ok(Some(2)));
+        assert_eq!(outputs[2].try_next(), Err(Blocked));
+        assert_eq!(outputs[2].len(), 2);
+        assert_eq!(outputs[0].try_next(), Ok(Some(0)));
+        assert_eq!(outputs[2].try_next(), Err(Blocked));
+        assert_eq!(outputs[1].next(), Some(1));
+        assert_eq!(outputs[2].try_next(), Ok(Some(5)));
+
+        // A dropped output does not block the others.
+        let mut outputs = (0..6).unaltern_bounded(2, 0);
+        drop(outputs.remove(1));
+        assert_eq!(std::iter::from_fn(|| outputs[0].try_next().unwrap()).collect::<Vec<_>>(), vec![0, 2, 4]);
+    }
+
+    #[test]
+    fn bounded_next_never_blocks() {
+        let mut outputs = (0..10).unaltern_bounded(2, 1);
+        assert_eq!(outputs[0].try_next(), Ok(Some(0)));
+        assert_eq!(outputs[0].try_next(), Ok(Some(2)));
+        assert_eq!(outputs[0].try_next(), Err(Blocked));
+        assert_eq!(outputs[0].by_ref().collect::<Vec<_>>(), vec![4, 6, 8]);
+        // The buffer of the output 1 holds more than its capacity.
+        assert_eq!(outputs[1].len(), 5);
+        assert_eq!(outputs[1].by_ref().collect::<Vec<_>>(), vec![1, 3, 5, 7, 9]);
+
+        for capacity in 0..3 {
+            let mut outputs = (0..10).unaltern_bounded(3, capacity);
+            assert_eq!(outputs[2].next(), Some(2));
+            assert_eq!(VecAltern::from(outputs).collect::<Vec<_>>(), vec![0, 1, 5, 3, 4, 8, 6, 7, 9]);
+
+            let mut outputs = (0..10).unaltern_bounded(3, capacity).into_iter();
+            let (first, second, third) = (outputs.next().unwrap(), outputs.next().unwrap(), outputs.next().unwrap());
+            assert_eq!(altern!(first, second, third).collect::<Vec<_>>(), (0..10).collect::<Vec<_>>());
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn zero_outputs() {
+        (0..3).unaltern(0);
+    }
+}
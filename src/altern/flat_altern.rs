@@ -0,0 +1,143 @@
+//! # FlatAltern
+//!
+//! The `flat_altern` module provides an iterator, `FlatAltern`, for alternating between the iterators yielded by
+//! an iterator, like `flatten` but in a round-robin fashion.
+//!
+//! ## Usage
+//!
+//! Call `flatten_altern()` on any iterator of iterators (or of anything implementing `IntoIterator`).
+//! The inner iterators are taken from it one at a time, when the first round reaches them, and their first element
+//! is yielded right away. The following rounds alternate between all of them, like a `VecAltern`.
+//!
+//! ## Examples
+//!
+//! ```rust
+//! use combin_iterator::altern::FlattenAltern;
+//! let files = vec!["a1\na2\na3", "b1", "c1\nc2"];
+//! let iter = files.iter().map(|file| file.lines()).flatten_altern();
+//!
+//! assert_eq!(iter.collect::<Vec<_>>(), vec!["a1", "b1", "c1", "a2", "c2", "a3"]);
+//! ```
+//!
+//! ## Notes
+//!
+//! - The first round takes all the inner iterators, so it never ends if there are infinitely many of them. To reach
+//!   all the elements of infinitely many iterators, see `Dovetail` in the module `diagonal`.
+//! - The inner iterators are stored without boxing, in a `VecAltern`.
+
+use super::VecAltern;
+
+/// Trait to alternate between the iterators yielded by an iterator.
+/// Implemented on Iterator
+pub trait FlattenAltern: Iterator
+where
+    Self::Item: IntoIterator
+{
+    /// Alternates between the inner iterators, taking them from `self` when the first round reaches them.
+    fn flatten_altern(self) -> FlatAltern<Self>
+    where
+        Self: Sized;
+}
+
+impl<O> FlattenAltern for O
+where
+    O: Iterator,
+    O::Item: IntoIterator
+{
+    fn flatten_altern(self) -> FlatAltern<Self>
+    where
+        Self: Sized
+    {
+        let altern = VecAltern::from(Vec::with_capacity(self.size_hint().0));
+        FlatAltern { outer: Some(self), altern }
+    }
+}
+
+/// Struct to alternate between the iterators yielded by an iterator, created by `FlattenAltern::flatten_altern`.
+pub struct FlatAltern<O>
+where
+    O: Iterator,
+    O::Item: IntoIterator
+{
+    /// The iterator of iterators, `None` once exhausted.
+    outer: Option<O>,
+    /// The inner iterators taken so far, which are not exhausted.
+    altern: VecAltern<'static, <O::Item as IntoIterator>::Item, <O::Item as IntoIterator>::IntoIter>,
+}
+
+impl<O> Iterator for FlatAltern<O>
+where
+    O: Iterator,
+    O::Item: IntoIterator
+{
+    type Item = <O::Item as IntoIterator>::Item;
+
+    /// Returns the first element of the next inner iterator during the first round, then alternates between them.
+    fn next(&mut self) -> Option<Self::Item> {
+        while let Some(outer) = self.outer.as_mut() {
+            match outer.next() {
+                Some(inner) => {
+                    let mut inner = inner.into_iter();
+                    if let Some(value) = inner.next() {
+                        self.altern.push(inner);
+                        return Some(value);
+                    }
+                }
+                None => self.outer = None,
+            }
+        }
+        self.altern.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (lower, upper) = self.altern.size_hint();
+        match &self.outer {
+            Some(outer) if outer.size_hint().1 != Some(0) => (lower, None),
+            _ => (lower, upper),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::FlattenAltern;
+
+    #[test]
+    fn flatten_altern() {
+        let iter = vec![vec![1, 4, 7, 9], vec![2, 5], vec![3, 6, 8]].into_iter().flatten_altern();
+        assert_eq!(iter.collect::<Vec<_>>(), vec![1, 2, 3, 4, 5, 6, 7, 8, 9]);
+
+        let iter = vec![vec![], vec![1, 3], vec![], vec![2]].into_iter().flatten_altern();
+        assert_eq!(iter.collect::<Vec<_>>(), vec![1, 2, 3]);
+
+        let iter = Vec::<Vec<i32>>::new().into_iter().flatten_altern();
+        assert_eq!(iter.count(), 0);
+    }
+
+    #[test]
+    fn takes_inner_iterators_lazily() {
+        let mut taken = 0;
+        let mut iter = (0..3).inspect(|_| taken += 1).map(|n| (0..2).map(move |k| n * 10 + k)).flatten_altern();
+        assert_eq!(iter.next(), Some(0));
+        assert_eq!(iter.next(), Some(10));
+        drop(iter);
+        assert_eq!(taken, 2);
+    }
+
+    #[test]
+    fn infinite_inner_iterators() {
+        let iter = (0..3).map(|n| (0..).map(move |k| n * 10 + k)).flatten_altern();
+        assert_eq!(iter.take(7).collect::<Vec<_>>(), vec![0, 10, 20, 1, 11, 21, 2]);
+    }
+
+    #[test]
+    fn size_hint() {
+        let mut iter = vec![vec![1, 2], vec![3]].into_iter().flatten_altern();
+        assert_eq!(iter.size_hint(), (0, None));
+        iter.next();
+        assert_eq!(iter.size_hint(), (1, None));
+        iter.next();
+        assert_eq!(iter.size_hint(), (1, Some(1)));
+        assert_eq!(iter.count(), 1);
+    }
+}
@@ -0,0 +1,308 @@
+//! # RandomAltern
+//!
+//! The `random_altern` module provides an iterator, `RandomAltern`, for interleaving multiple iterators in a random
+//! but reproducible order, keeping the order of the elements of each iterator.
+//!
+//! ## Usage
+//!
+//! To use `RandomAltern`, create a new instance with `RandomAltern::new(seed)` and add iterators using the `add`
+//! method (or `add_and` for a build pattern), or with `RandomAltern::seeded(seed, Vec<I>)`.
+//! The `next` method will then yield the next element of a randomly chosen iterator, until all are exhausted. The
+//! same seed and the same iterators always give the same order.<br/>
+//! By default, each running iterator is chosen with the same probability. If the iterators implement
+//! `ExactSizeIterator`, `proportional()` chooses them with a probability proportional to their remaining length
+//! instead, which makes every interleaving equally likely.
+//!
+//! ## Examples
+//!
+//! ```rust
+//! use combin_iterator::altern::RandomAltern;
+//! let vec1 = vec![1, 2, 3];
+//! let vec2 = vec![10, 20];
+//!
+//! let iter = RandomAltern::new(42).add_and(vec1.iter()).add_and(vec2.iter());
+//! let shuffled = iter.collect::<Vec<_>>();
+//! assert_eq!(shuffled.len(), 5);
+//! assert_eq!(shuffled.iter().filter(|value| ***value < 10).collect::<Vec<_>>(), vec![&&1, &&2, &&3]);
+//!
+//! // The same seed gives the same interleaving.
+//! let again = RandomAltern::new(42).add_and(vec1.iter()).add_and(vec2.iter());
+//! assert_eq!(again.collect::<Vec<_>>(), shuffled);
+//!
+//! // The choice can be proportional to the remaining lengths instead.
+//! let iter = RandomAltern::seeded(7, vec![vec1.iter(), vec2.iter()]).proportional();
+//! assert_eq!(iter.collect::<Vec<_>>(), vec![&1, &2, &10, &20, &3]);
+//! ```
+//!
+//! ## Notes
+//!
+//! - The random numbers come from a small built-in generator (SplitMix64), good enough for test data, but not for
+//!   cryptography. The order for a given seed may change between major versions of this crate.
+//! - `RandomAltern` implements `SourceTagged`: the index of an iterator is the order it was added in.
+
+use std::marker::PhantomData;
+
+use super::SourceTagged;
+use crate::Marker;
+
+/// Struct to interleave several iterators in a random order
+///
+/// Each element comes from an iterator drawn among the running ones, uniformly or proportionally to their remaining
+/// length (see `proportional`), and an exhausted iterator is dropped when it is drawn. `I` is the type of the
+/// iterators, boxed by default (see the module `altern`).
+pub struct RandomAltern<'a, A, I = Box<dyn Iterator<Item = A> + 'a>>
+where
+    I: Iterator<Item = A>
+{
+    /// The running iterators, with the order they were added in.
+    iters: Vec<(usize, I)>,
+    count: usize,
+    rng: SplitMix64,
+    /// The length of an iterator, if the choice is proportional to it.
+    len: Option<fn(&I) -> usize>,
+    _marker: Marker<'a, A>,
+}
+
+impl<'a, A> RandomAltern<'a, A> {
+    /// Creates a new instance of a `RandomAltern` iterator, whose order is given by `seed`.
+    pub fn new(seed: u64) -> Self {
+        Self::seeded(seed, Vec::new())
+    }
+
+    /// Adds an iterator to the `RandomAltern` instance.
+    ///
+    /// # Returns
+    ///
+    /// The updated `RandomAltern` instance with the added iterator, to use like a builder.
+    pub fn add_and(mut self, iterator: impl Iterator<Item = A> + 'a) -> Self {
+        self.push(Box::new(iterator));
+        self
+    }
+
+    /// Adds an iterator to the `RandomAltern` instance.
+    pub fn add(&mut self, iterator: impl Iterator<Item = A> + 'a) {
+        self.push(Box::new(iterator));
+    }
+}
+
+impl<'a, A, I> RandomAltern<'a, A, I>
+where
+    I: Iterator<Item = A>
+{
+    /// Creates a `RandomAltern` over iterators of one concrete type, without boxing them, whose order is given by
+    /// `seed`.
+    pub fn seeded(seed: u64, iters: Vec<I>) -> Self {
+        Self {
+            count: iters.len(),
+            iters: iters.into_iter().enumerate().collect(),
+            rng: SplitMix64 { state: seed },
+            len: None,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Adds an iterator of the stored type, without boxing it.
+    pub fn push(&mut self, iterator: I) {
+        self.iters.push((self.count, iterator));
+        self.count += 1;
+    }
+
+    /// Chooses the index in `iters` of the next iterator to visit.
+    fn choose(&mut self) -> usize {
+        let Some(len) = self.len else {
+            return self.rng.below(self.iters.len());
+        };
+        let total = self.iters.iter().map(|(_, iter)| len(iter)).sum::<usize>();
+        if total == 0 {
+            return 0;
+        }
+        let element = self.rng.below(total);
+        self.iter_of(element, len)
+    }
+
+    /// Returns the index in `iters` of the iterator holding the `element`th of their remaining elements, the lengths
+    /// of the iterators being given by `len`.
+    fn iter_of(&self, mut left: usize, len: fn(&I) -> usize) -> usize {
+        self.iters.iter().position(|(_, iter)| {
+            let len = len(iter);
+            left = match left.checked_sub(len) {
+                Some(left) => left,
+                None => return true,
+            };
+            false
+        }).expect("the chosen element is in one of the iterators")
+    }
+}
+
+impl<'a, A, I> RandomAltern<'a, A, I>
+where
+    I: ExactSizeIterator<Item = A>
+{
+    /// Chooses each iterator with a probability proportional to its remaining length, instead of uniformly, so that
+    /// every interleaving of the elements is equally likely.
+    ///
+    /// # Example
+    /// ```
+    /// use combin_iterator::altern::RandomAltern;
+    /// let iter = RandomAltern::seeded(1, vec![0..4, 10..13, 20..22]).proportional();
+    /// assert_eq!(iter.collect::<Vec<_>>(), vec![10, 11, 20, 0, 1, 21, 12, 2, 3]);
+    /// ```
+    pub fn proportional(mut self) -> Self {
+        self.len = Some(ExactSizeIterator::len);
+        self
+    }
+}
+
+impl<'a, A, I> Iterator for RandomAltern<'a, A, I>
+where
+    I: Iterator<Item = A>
+{
+    type Item = A;
+
+    fn next(&mut self) -> Option<A> {
+        self.next_tagged().map(|(_, value)| value)
+    }
+
+    /// Whatever the draws, every element of the running iterators is yielded, so the bounds add up.
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iters.iter().fold((0usize, Some(0usize)), |(lower, upper), (_, iter)| {
+            let hint = iter.size_hint();
+            (lower.saturating_add(hint.0), upper.zip(hint.1).and_then(|(u1, u2)| u1.checked_add(u2)))
+        })
+    }
+}
+
+impl<'a, A, I> SourceTagged for RandomAltern<'a, A, I>
+where
+    I: Iterator<Item = A>
+{
+    /// Returns the number of iterators added, including the exhausted ones.
+    fn source_count(&self) -> usize {
+        self.count
+    }
+
+    /// Returns the next element, with the index of its iterator in the order they were added.
+    fn next_tagged(&mut self) -> Option<(usize, A)> {
+        while !self.iters.is_empty() {
+            let index = self.choose();
+            let (source, iter) = &mut self.iters[index];
+            match iter.next() {
+                Some(value) => return Some((*source, value)),
+                None => drop(self.iters.swap_remove(index)),
+            }
+        }
+        None
+    }
+}
+
+impl<'a, A, I> ExactSizeIterator for RandomAltern<'a, A, I>
+where
+    I: ExactSizeIterator<Item = A> {}
+
+/// The SplitMix64 pseudo-random generator: a 64 bits counter, mixed by a bijection.
+struct SplitMix64 {
+    state: u64,
+}
+
+impl SplitMix64 {
+    fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    /// Returns a number in `0..bound`, without bias (Lemire's method).
+    fn below(&mut self, bound: usize) -> usize {
+        let bound = bound as u64;
+        let threshold = bound.wrapping_neg() % bound;
+        loop {
+            let product = u128::from(self.next_u64()) * u128::from(bound);
+            if product as u64 >= threshold {
+                return (product >> 64) as usize;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{RandomAltern, SplitMix64};
+    use crate::altern::SourceTagged;
+
+    #[test]
+    fn keeps_the_order_of_each_iterator() {
+        for seed in 0..50 {
+            let iter = RandomAltern::new(seed).add_and(0..10).add_and(10..15).add_and(15..30);
+            let mut sources = vec![Vec::new(); 3];
+            let mut count = 0;
+            for (source, value) in iter.enumerate_sources() {
+                sources[source].push(value);
+                count += 1;
+            }
+            assert_eq!(count, 30);
+            assert_eq!(sources, vec![(0..10).collect::<Vec<_>>(), (10..15).collect(), (15..30).collect()]);
+        }
+    }
+
+    #[test]
+    fn reproducible() {
+        let order = |seed| RandomAltern::seeded(seed, vec![0..20, 20..40]).collect::<Vec<_>>();
+        assert_eq!(order(3), order(3));
+        assert_ne!(order(3), order(4));
+    }
+
+    #[test]
+    fn uniform() {
+        let order = |seed| RandomAltern::seeded(seed, vec![0..4, 10..13, 20..22]).collect::<Vec<_>>();
+        assert_eq!(order(0), vec![20, 10, 0, 21, 1, 2, 3, 11, 12]);
+        assert_eq!(order(1), vec![10, 20, 21, 11, 12, 0, 1, 2, 3]);
+        assert_eq!(order(2), vec![10, 20, 11, 21, 0, 12, 1, 2, 3]);
+    }
+
+    #[test]
+    fn proportional() {
+        let order = |seed| RandomAltern::seeded(seed, vec![0..4, 10..13, 20..22]).proportional().collect::<Vec<_>>();
+        assert_eq!(order(0), vec![20, 0, 1, 21, 2, 10, 3, 11, 12]);
+        assert_eq!(order(1), vec![10, 11, 20, 0, 1, 21, 12, 2, 3]);
+        assert_eq!(order(2), vec![10, 11, 12, 20, 0, 1, 21, 2, 3]);
+    }
+
+    #[test]
+    fn proportional_covers_each_element() {
+        // Each iterator is chosen by exactly as many of the numbers `below` can return as it has elements left.
+        let iter = RandomAltern::seeded(0, vec![0..0, 0..4, 10..11, 20..22, 30..30, 40..43]).proportional();
+        let len = iter.len.unwrap();
+        let mut counts = vec![0; iter.iters.len()];
+        for element in 0..iter.len() {
+            counts[iter.iter_of(element, len)] += 1;
+        }
+        assert_eq!(counts, vec![0, 4, 1, 2, 0, 3]);
+    }
+
+    #[test]
+    fn exhausted_and_empty() {
+        let iter = RandomAltern::new(1).add_and(std::iter::empty()).add_and(0..3).add_and(std::iter::empty());
+        assert_eq!(iter.collect::<Vec<_>>(), vec![0, 1, 2]);
+
+        let iter = RandomAltern::<i32>::new(1);
+        assert_eq!(iter.size_hint(), (0, Some(0)));
+        assert_eq!(iter.count(), 0);
+
+        let iter = RandomAltern::seeded(1, vec![0..0, 0..3, 3..3]).proportional();
+        assert_eq!(iter.len(), 3);
+        assert_eq!(iter.collect::<Vec<_>>(), vec![0, 1, 2]);
+    }
+
+    #[test]
+    fn below() {
+        // The first outputs of SplitMix64 for the seed 0.
+        let mut rng = SplitMix64 { state: 0 };
+        assert_eq!((0..2).map(|_| rng.next_u64()).collect::<Vec<_>>(), vec![0xE220_A839_7B1D_CDAF, 0x6E78_9E6A_A1B9_65F4]);
+
+        let mut rng = SplitMix64 { state: 0 };
+        assert_eq!((0..10).map(|_| rng.below(5)).collect::<Vec<_>>(), vec![4, 2, 0, 4, 0, 1, 0, 3, 1, 4]);
+        assert_eq!(rng.below(1), 0);
+    }
+}
@@ -0,0 +1,216 @@
+//! # Rounds
+//!
+//! The `rounds` module provides an adapter, `Rounds`, to see the rounds of an alternation as units: each round holds
+//! one element from every source still running.
+//!
+//! ## Usage
+//!
+//! Call `rounds` on any alternation implementing `SourceTagged` (a `VecAltern`, an `ArrayAltern`, a `TupleAltern`
+//! or a `BiAltern`, whose tree is flattened by `with_nested_sources`). `Rounds` is an iterator of `Vec<Item>`, one
+//! per round. To avoid the allocation of a `Vec`, `next_round` gives instead a `Round`, an iterator over the elements
+//! of the next round borrowing the `Rounds`.
+//!
+//! ## Examples
+//!
+//! ```rust
+//! use combin_iterator::altern::{SourceTagged, VecAltern};
+//! let vec1 = vec![1, 4, 7, 9];
+//! let vec2 = vec![2, 5];
+//! let vec3 = vec![3, 6, 8];
+//!
+//! let iter = VecAltern::new().add_and(vec1.iter()).add_and(vec2.iter()).add_and(vec3.iter());
+//! assert_eq!(iter.rounds().collect::<Vec<_>>(), vec![vec![&1, &2, &3], vec![&4, &5, &6], vec![&7, &8], vec![&9]]);
+//!
+//! // Without allocating:
+//! let iter = VecAltern::new().add_and(vec1.iter()).add_and(vec2.iter()).add_and(vec3.iter());
+//! let mut rounds = iter.rounds();
+//! let mut sums = vec![];
+//! while let Some(round) = rounds.next_round() {
+//!     sums.push(round.sum::<i32>());
+//! }
+//! assert_eq!(sums, vec![6, 15, 15, 9]);
+//! ```
+//!
+//! ## Notes
+//!
+//! - A round ends just before an element whose source already gave an element in the round, so the rounds follow
+//!   the alternation without knowing how many sources are running. An exhausted source leaves the next rounds,
+//!   and a source added during the iteration joins them.
+//! - With weights or chunks, a source gives several elements in a row or in a period, so the rounds are split at
+//!   each of them. Likewise, a `BiAltern` tree whose number of sources is not a power of 2 does not visit every
+//!   source once before visiting one again (see the section `Common mistake` in the module `bi_altern`).
+//! - A `Round` dropped before its end skips its remaining elements, so that the next round starts at a new round.
+
+use super::SourceTagged;
+
+/// An iterator yielding the rounds of an alternation as `Vec`, created by `SourceTagged::rounds`.
+pub struct Rounds<T: SourceTagged> {
+    iter: T,
+    pending: Option<(usize, T::Item)>,
+    seen: Vec<usize>,
+    round: usize,
+}
+
+impl<T: SourceTagged> Rounds<T> {
+    pub(crate) fn new(iter: T) -> Self {
+        Self { iter, pending: None, seen: Vec::new(), round: 0 }
+    }
+
+    /// Returns an iterator over the elements of the next round, or `None` if the alternation is exhausted.
+    ///
+    /// # Example
+    /// ```
+    /// use combin_iterator::altern::{SourceTagged, VecAltern};
+    /// let mut rounds = VecAltern::from(vec![0..2, 10..13]).rounds();
+    ///
+    /// assert_eq!(rounds.next_round().unwrap().collect::<Vec<_>>(), vec![0, 10]);
+    /// assert_eq!(rounds.next_round().unwrap().max(), Some(11));
+    /// assert_eq!(rounds.next_round().unwrap().count(), 1);
+    /// assert!(rounds.next_round().is_none());
+    /// ```
+    pub fn next_round(&mut self) -> Option<Round<'_, T>> {
+        let first = self.pending.take().or_else(|| self.iter.next_tagged())?;
+        self.round += 1;
+        self.see(first.0);
+        Some(Round { rounds: self, first: Some(first.1), done: false })
+    }
+
+    /// Returns the underlying alternation.
+    ///
+    /// The first element of the next round, if it was already taken from the alternation, is lost.
+    pub fn into_inner(self) -> T {
+        self.iter
+    }
+
+    /// Records that the source `source` gave an element in the current round, and returns whether it already had.
+    fn see(&mut self, source: usize) -> bool {
+        if source >= self.seen.len() {
+            self.seen.resize(self.iter.source_count().max(source + 1), 0);
+        }
+        std::mem::replace(&mut self.seen[source], self.round) == self.round
+    }
+
+    /// Returns the next element of the current round, keeping the first element of the next round aside.
+    fn next_in_round(&mut self) -> Option<T::Item> {
+        let (source, value) = self.iter.next_tagged()?;
+        if self.see(source) {
+            self.pending = Some((source, value));
+            return None;
+        }
+        Some(value)
+    }
+}
+
+impl<T: SourceTagged> Iterator for Rounds<T> {
+    type Item = Vec<T::Item>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.next_round().map(Iterator::collect)
+    }
+
+    /// Returns at least one round if there is an element left, and at most one round per element.
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let pending = usize::from(self.pending.is_some());
+        let (lower, upper) = self.iter.size_hint();
+        (lower.saturating_add(pending).min(1), upper.and_then(|upper| upper.checked_add(pending)))
+    }
+}
+
+/// An iterator over the elements of a round, created by `Rounds::next_round`.
+pub struct Round<'r, T: SourceTagged> {
+    rounds: &'r mut Rounds<T>,
+    first: Option<T::Item>,
+    done: bool,
+}
+
+impl<T: SourceTagged> Iterator for Round<'_, T> {
+    type Item = T::Item;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if let Some(first) = self.first.take() {
+            return Some(first);
+        }
+        if self.done {
+            return None;
+        }
+        let next = self.rounds.next_in_round();
+        self.done = next.is_none();
+        next
+    }
+
+    /// Returns at most the number of sources, and of elements left.
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let first = usize::from(self.first.is_some());
+        if self.done {
+            return (first, Some(first));
+        }
+        let upper = self.rounds.iter.size_hint().1.map_or(usize::MAX, |upper| upper.saturating_add(first));
+        (first, Some(upper.min(self.rounds.iter.source_count().max(first))))
+    }
+}
+
+impl<T: SourceTagged> Drop for Round<'_, T> {
+    /// Skips the elements left in the round.
+    fn drop(&mut self) {
+        while !self.done {
+            self.done = self.rounds.next_in_round().is_none();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::altern::{BiAltern, SourceTagged, VecAltern};
+
+    #[test]
+    fn vec_altern() {
+        let iter = VecAltern::from(vec![0..3, 10..11, 20..23, 30..32]);
+        assert_eq!(iter.rounds().collect::<Vec<_>>(), vec![vec![0, 10, 20, 30], vec![1, 21, 31], vec![2, 22]]);
+
+        let iter = VecAltern::from(Vec::<std::ops::Range<i32>>::new());
+        assert_eq!(iter.rounds().count(), 0);
+    }
+
+    #[test]
+    fn add_during_iteration() {
+        let mut iter = VecAltern::from(vec![0..3, 10..13]);
+        let handle = iter.handle();
+        let mut rounds = iter.rounds();
+        assert_eq!(rounds.next(), Some(vec![0, 10]));
+        handle.push(20..22);
+        assert_eq!(rounds.collect::<Vec<_>>(), vec![vec![1, 11, 20], vec![2, 12, 21]]);
+    }
+
+    #[test]
+    fn bi_altern_tree() {
+        let iter = BiAltern::new(BiAltern::new(0..2, 10..13), BiAltern::new(20..21, 30..33)).with_nested_sources();
+        assert_eq!(iter.rounds().collect::<Vec<_>>(), vec![vec![0, 20, 10, 30], vec![1, 31, 11], vec![32, 12]]);
+    }
+
+    #[test]
+    fn next_round() {
+        let mut rounds = VecAltern::from(vec![0..3, 10..13, 20..22]).rounds();
+        let mut round = rounds.next_round().unwrap();
+        assert_eq!(round.size_hint(), (1, Some(3)));
+        assert_eq!(round.next(), Some(0));
+        drop(round);
+
+        assert_eq!(rounds.next_round().unwrap().collect::<Vec<_>>(), vec![1, 11, 21]);
+        let mut round = rounds.next_round().unwrap();
+        assert_eq!(round.by_ref().collect::<Vec<_>>(), vec![2, 12]);
+        assert_eq!(round.next(), None);
+        assert_eq!(round.size_hint(), (0, Some(0)));
+        drop(round);
+        assert!(rounds.next_round().is_none());
+    }
+
+    #[test]
+    fn size_hint() {
+        let rounds = VecAltern::from(vec![0..3, 10..13]).rounds();
+        assert_eq!(rounds.size_hint(), (1, Some(6)));
+
+        let mut rounds = VecAltern::from(vec![0..1, 10..11]).rounds();
+        rounds.next();
+        assert_eq!(rounds.size_hint(), (0, Some(0)));
+    }
+}
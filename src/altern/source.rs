@@ -0,0 +1,233 @@
+//! # Source
+//!
+//! The `source` module provides the `SourceTagged` trait, implemented by the alternations able to tell from which of
+//! their iterators (their sources) each element comes.
+//!
+//! ## Usage
+//!
+//! Call `enumerate_sources` on a `VecAltern`, an `ArrayAltern` or a `TupleAltern` to get an iterator of
+//! `(source_index, item)` pairs. The index of a source is its position in the alternation: the order it was added
+//! in for `VecAltern`, and its position in the array or the tuple otherwise. It does not change when other sources
+//! are exhausted.<br/>
+//! `BiAltern` tags its first iterator with `0` and its second one with `1`. With `BiAltern::with_nested_sources`,
+//! nested `BiAltern` are flattened instead, and the other iterators of the tree are wrapped into a `Source`, which is
+//! an iterator with a single source (see the section `Sources` in the module `bi_altern`).
+//!
+//! `rounds` groups the elements by round instead, one element from every running source (see the module `rounds`).
+//!
+//! ## Examples
+//!
+//! ```rust
+//! use combin_iterator::altern::{SourceTagged, VecAltern};
+//! let vec1 = vec![1, 4, 7, 9];
+//! let vec2 = vec![2, 5];
+//! let vec3 = vec![3, 6, 8];
+//!
+//! let iter = VecAltern::new().add_and(vec1.iter()).add_and(vec2.iter()).add_and(vec3.iter());
+//!
+//! assert_eq!(
+//!     iter.enumerate_sources().collect::<Vec<_>>(),
+//!     vec![(0, &1), (1, &2), (2, &3), (0, &4), (1, &5), (2, &6), (0, &7), (2, &8), (0, &9)]
+//! );
+//! ```
+
+use std::iter::FusedIterator;
+
+use super::Rounds;
+
+/// Trait of the alternations which know from which source each element comes.
+pub trait SourceTagged: Iterator {
+    /// Returns the number of sources. The sources are numbered from `0` to `source_count() - 1`.
+    fn source_count(&self) -> usize;
+
+    /// Returns the next element, like `next`, with the index of the source it comes from.
+    fn next_tagged(&mut self) -> Option<(usize, Self::Item)>;
+
+    /// Creates an iterator which yields the elements with the index of the source they come from.
+    fn enumerate_sources(self) -> EnumerateSources<Self>
+    where
+        Self: Sized
+    {
+        EnumerateSources { iter: self }
+    }
+
+    /// Creates an iterator which yields the elements by round, a round holding one element from every source still
+    /// running (see the module `rounds`).
+    fn rounds(self) -> Rounds<Self>
+    where
+        Self: Sized
+    {
+        Rounds::new(self)
+    }
+}
+
+/// The identifier of an iterator added to a `VecAltern`, to pause, resume or remove it.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct SourceId(pub(crate) usize);
+
+impl SourceId {
+    /// Returns the index of the source, as tagged by `SourceTagged::next_tagged`.
+    pub fn index(self) -> usize {
+        self.0
+    }
+}
+
+/// An iterator yielding `(source_index, item)` pairs, created by `SourceTagged::enumerate_sources`.
+pub struct EnumerateSources<T> {
+    iter: T,
+}
+
+impl<T> EnumerateSources<T> {
+    /// Returns the underlying alternation.
+    pub fn into_inner(self) -> T {
+        self.iter
+    }
+}
+
+impl<T: SourceTagged> Iterator for EnumerateSources<T> {
+    type Item = (usize, T::Item);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.iter.next_tagged()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+impl<T: SourceTagged + ExactSizeIterator> ExactSizeIterator for EnumerateSources<T> {}
+
+/// An iterator with a single source, to use as a leaf of a `BiAltern` tree tagged with `with_nested_sources`.
+///
+/// It yields the elements of the wrapped iterator unchanged.
+#[derive(Clone, Debug)]
+pub struct Source<I> {
+    iter: I,
+}
+
+impl<I: Iterator> Source<I> {
+    /// Wraps an iterator into a source.
+    pub fn new(iter: I) -> Self {
+        Self { iter }
+    }
+}
+
+impl<I: Iterator> Iterator for Source<I> {
+    type Item = I::Item;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.iter.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+impl<I: DoubleEndedIterator> DoubleEndedIterator for Source<I> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.iter.next_back()
+    }
+}
+
+impl<I: ExactSizeIterator> ExactSizeIterator for Source<I> {}
+
+impl<I: FusedIterator> FusedIterator for Source<I> {}
+
+impl<I: Iterator> SourceTagged for Source<I> {
+    fn source_count(&self) -> usize {
+        1
+    }
+
+    fn next_tagged(&mut self) -> Option<(usize, Self::Item)> {
+        self.iter.next().map(|value| (0, value))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::altern::{ArrayAltern, BiAltern, Source, SourceTagged, TupleAltern, VecAltern};
+
+    #[test]
+    fn vec_altern() {
+        let vec1 = [1, 4, 7, 9];
+        let vec2 = [2, 5];
+        let vec3 = [3, 6, 8];
+
+        let iter = VecAltern::new().add_and(vec1.iter()).add_and(vec2.iter()).add_and(vec3.iter());
+        assert_eq!(iter.source_count(), 3);
+        assert_eq!(
+            iter.enumerate_sources().collect::<Vec<_>>(),
+            vec![(0, &1), (1, &2), (2, &3), (0, &4), (1, &5), (2, &6), (0, &7), (2, &8), (0, &9)]
+        );
+    }
+
+    #[test]
+    fn vec_altern_stable_after_exhaustion() {
+        let vec1 = [1];
+        let vec2 = [2, 4];
+        let vec3 = [3, 5];
+        let vec4 = [6];
+
+        let mut iter = VecAltern::new().add_and(vec1.iter()).add_and(vec2.iter()).add_and(vec3.iter());
+        assert_eq!(iter.next_tagged(), Some((0, &1)));
+        assert_eq!(iter.next_tagged(), Some((1, &2)));
+        assert_eq!(iter.next_tagged(), Some((2, &3)));
+        assert_eq!(iter.next_tagged(), Some((1, &4)));
+        iter.add(vec4.iter());
+        assert_eq!(iter.enumerate_sources().collect::<Vec<_>>(), vec![(2, &5), (3, &6)]);
+    }
+
+    #[test]
+    fn array_and_tuple_altern() {
+        let iter = ArrayAltern::new([0..2, 2..3, 3..5]);
+        assert_eq!(iter.enumerate_sources().collect::<Vec<_>>(), vec![(0, 0), (1, 2), (2, 3), (0, 1), (2, 4)]);
+
+        let iter = TupleAltern::new((0..2, 2..3, (3..5).rev()));
+        assert_eq!(iter.source_count(), 3);
+        assert_eq!(iter.enumerate_sources().collect::<Vec<_>>(), vec![(0, 0), (1, 2), (2, 4), (0, 1), (2, 3)]);
+    }
+
+    #[test]
+    fn nested_bi_altern() {
+        let vec1 = [1, 1];
+        let vec2 = [2, 2];
+        let vec3 = [3, 3];
+        let vec4 = [4, 4, 4];
+
+        let iter = BiAltern::new(BiAltern::new(vec1.iter(), vec3.iter()), BiAltern::new(vec2.iter(), vec4.iter()));
+        assert_eq!(iter.source_count(), 2);
+        assert_eq!(iter.clone().enumerate_sources().filter(|(source, _)| *source == 1).count(), 5);
+
+        let iter = iter.with_nested_sources();
+        assert_eq!(iter.source_count(), 4);
+        assert_eq!(
+            iter.enumerate_sources().collect::<Vec<_>>(),
+            vec![(0, &1), (2, &2), (1, &3), (3, &4), (0, &1), (2, &2), (1, &3), (3, &4), (3, &4)]
+        );
+    }
+
+    #[test]
+    fn any_leaf() {
+        let vec1 = [1, 1];
+        let vec2 = [2];
+        let vec3 = [3, 3];
+
+        let iter = BiAltern::new(vec1.iter(), vec2.iter());
+        assert_eq!(iter.source_count(), 2);
+        assert_eq!(iter.enumerate_sources().collect::<Vec<_>>(), vec![(0, &1), (1, &2), (0, &1)]);
+
+        let boxed: Box<dyn Iterator<Item = &i32> + Send + Sync> = Box::new(vec3.iter());
+        let scan = vec1.iter().scan((), |_, value| Some(value));
+        let iter = BiAltern::new(BiAltern::new(scan, vec2.iter()), Source::new(boxed)).with_nested_sources();
+        assert_eq!(iter.source_count(), 3);
+        assert_eq!(iter.enumerate_sources().collect::<Vec<_>>(), vec![(0, &1), (2, &3), (1, &2), (2, &3), (0, &1)]);
+    }
+
+    #[test]
+    fn bi_altern_stable_after_exhaustion() {
+        let iter = BiAltern::new(BiAltern::new(0..1, 1..2), Source::new(2..5)).with_nested_sources();
+        assert_eq!(iter.enumerate_sources().collect::<Vec<_>>(), vec![(0, 0), (2, 2), (1, 1), (2, 3), (2, 4)]);
+    }
+}
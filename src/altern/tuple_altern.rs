@@ -0,0 +1,122 @@
+//! # TupleAltern
+//!
+//! The `tuple_altern` module provides an iterator, `TupleAltern`, for alternately traversing a tuple of iterators
+//! of different types.
+//!
+//! ## Usage
+//!
+//! To use `TupleAltern`, create a new instance with `TupleAltern::new((Iter1, Iter2, ...))`, with up to 12 iterators
+//! yielding the same `Item`.
+//! The `next` method will then yield elements from the iterators in a round-robin fashion until all iterators are exhausted.<br/>
+//! The order is the flat round-robin order of `VecAltern` (and of the `altern!` macro), but the iterators are neither boxed
+//! nor called through dynamic dispatch.<br/>
+//! Like `BiAltern`, `TupleAltern` implements `ExactSizeIterator` if all iterators implement it, and if they additionally
+//! implement `DoubleEndedIterator`, then so does `TupleAltern`.
+//!
+//! ## Examples
+//!
+//! ```rust
+//! use combin_iterator::altern::TupleAltern;
+//! let vec1 = vec![1, 4, 7, 9];
+//! let vec2 = vec![2, 5];
+//!
+//! let iter = TupleAltern::new((vec1.iter().copied(), vec2.iter().copied(), (3..9).step_by(3)));
+//! assert_eq!(iter.collect::<Vec<_>>(), vec![1, 2, 3, 4, 5, 6, 7, 9]);
+//!
+//! let iter = TupleAltern::new((vec1.iter().copied(), vec2.iter().copied(), (3..9).step_by(3)));
+//! assert_eq!(iter.len(), 8);
+//! assert_eq!(iter.rev().collect::<Vec<_>>(), vec![9, 7, 6, 5, 4, 3, 2, 1]);
+//! ```
+
+use crate::macros::impl_tuple_altern;
+
+/// TupleAltern struct, to altern between the iterators of a tuple.
+pub struct TupleAltern<T> {
+    iters: T,
+    exhausted: u16,
+    current: usize,
+}
+
+impl<T> TupleAltern<T> {
+    /// Creates a new instance of a `TupleAltern` iterator.
+    ///
+    /// `TupleAltern` is an iterator for tuples of 1 to 12 iterators with the same `Item`.
+    pub fn new(iters: T) -> Self {
+        Self {
+            iters,
+            exhausted: 0,
+            current: 0,
+        }
+    }
+}
+
+impl_tuple_altern!(
+    (0 I0) (1 I1) (2 I2) (3 I3) (4 I4) (5 I5)
+    (6 I6) (7 I7) (8 I8) (9 I9) (10 I10) (11 I11)
+);
+
+#[cfg(test)]
+mod tests {
+    use super::TupleAltern;
+
+    #[test]
+    fn normal_usage() {
+        let vec1 = [1, 4, 7, 9];
+        let vec2 = [2, 5];
+
+        let iter = TupleAltern::new((vec1.iter().copied(), vec2.iter().copied(), (3..9).step_by(3)));
+
+        assert_eq!(iter.collect::<Vec<_>>(), vec![1,2,3,4,5,6,7,9]);
+    }
+
+    #[test]
+    fn single() {
+        let iter = TupleAltern::new((0..3,));
+        assert_eq!(iter.collect::<Vec<_>>(), vec![0, 1, 2]);
+    }
+
+    #[test]
+    fn twelve() {
+        let iter = TupleAltern::new((
+            0..1, 1..2, 2..3, 3..4, 4..5, 5..6,
+            6..7, 7..8, 8..9, 9..10, 10..11, (11..13).step_by(1),
+        ));
+        assert_eq!(iter.collect::<Vec<_>>(), (0..13).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn reverse() {
+        let vec1 = [1, 4, 7, 9];
+        let vec2 = [2, 5];
+        let vec3 = [3, 6, 8];
+
+        let iter = TupleAltern::new((vec1.iter(), vec2.iter(), vec3.iter().take(3))).rev();
+
+        assert_eq!(iter.collect::<Vec<_>>(), vec![&9,&8,&7,&6,&5,&4,&3,&2,&1]);
+    }
+
+    #[test]
+    fn next_and_next_back() {
+        let vec1 = [1, 4, 7, 9];
+        let vec2 = [2, 5];
+        let vec3 = [3, 6, 8];
+
+        let mut iter = TupleAltern::new((vec1.iter(), vec2.iter(), vec3.iter().take(3)));
+
+        assert_eq!(iter.next(), Some(&1));
+        assert_eq!(iter.next_back(), Some(&9));
+        assert_eq!(iter.next_back(), Some(&8));
+        assert_eq!(iter.next(), Some(&2));
+        assert_eq!(iter.next(), Some(&3));
+        assert_eq!(iter.next_back(), Some(&7));
+        assert_eq!(iter.len(), 3);
+        assert_eq!(iter.collect::<Vec<_>>(), vec![&4,&5,&6]);
+    }
+
+    #[test]
+    fn size_hint() {
+        let vec1 = [1, 4, 7, 9];
+        let iter = TupleAltern::new((vec1.iter(), vec1.iter().filter(|x| **x > 4), std::iter::empty()));
+        assert_eq!(iter.size_hint(), (4, Some(8)));
+    }
+}
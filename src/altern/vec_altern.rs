@@ -41,29 +41,197 @@
 //! - The `altern!` macro provides a convenient way to create an `Altern` iterator with a cleaner syntax, and
 //!   a little performance optimization (with the function: `with_capacity`, like in `Vec`). If you know at
 //!   compile time how many iter you will altern between, then use the `altern!` macro.
+//! - The iterators are kept in a ring, so an exhausted iterator is removed in constant time, even when
+//!   alternating between thousands of short iterators.
+//! - `size_hint` sums the hints of the remaining iterators, so collecting a `VecAltern` allocates only once when
+//!   the iterators know their size.
+//! - If all the iterators have the same type, `VecAltern::from(Vec<I>)` stores them without boxing. `VecAltern` then
+//!   implements `ExactSizeIterator` and `DoubleEndedIterator` if the iterators do, and `rev` yields exactly the mirror
+//!   of the forward order, even with iterators of different lengths.
+//! - `collect::<VecAltern<_>>()` builds one from an iterator of iterators, boxing them like `add`. To take them
+//!   from the iterator only when the first round reaches them, see `FlattenAltern::flatten_altern`.
+//! - `add` returns a `SourceId`, to `pause`, `resume` or `remove` the iterator later. A paused or resumed iterator
+//!   is visited at the end of the round, like an added one. `VecAltern::handle` gives a `VecAlternHandle` doing the
+//!   same while the `VecAltern` is consumed; its changes are applied before the next element, so it returns their
+//!   results as `PendingResult`s.
+//! - `with_chunk(k)` takes up to `k` consecutive elements from each iterator before moving to the next one, for
+//!   formats interleaving blocks rather than single elements. `altern!(chunk: k; ...)` builds one.
+//! - `VecAltern` implements `Clone` if the iterators do, to replay the alternation. `CloneVecAltern` mixes iterators of
+//!   different types implementing `Clone`, boxed as `CloneIterator` trait objects. `Debug` shows the running
+//!   iterators, from the one visited next, without requiring the iterators to implement it.
+//! - Once `next` returned `None`, it keeps returning it until an iterator is added or resumed, so `VecAltern` does
+//!   not implement `FusedIterator`. `into_fused` freezes its iterators, once no handle is alive, into a
+//!   `FusedVecAltern` which does.
+//! - The boxed iterators of `VecAltern` are neither `Send` nor `Sync`. `SendVecAltern` and `SyncVecAltern` box them
+//!   with these bounds, so they can be moved or shared between threads. `altern!(send: ...)` builds a `SendVecAltern`,
+//!   and `altern!(chunk: k; send: ...)` a chunked one.
+//! - `with_termination(Termination::Shortest)` stops at the first exhausted iterator instead of skipping it. With
+//!   it, `next_back` first drops the elements which will never be reached, which takes a time linear in the number
+//!   of iterators.
 
+use std::collections::VecDeque;
+use std::fmt;
+use std::marker::PhantomData;
+use std::sync::atomic::Ordering;
+use std::sync::Arc;
+
+use super::{complete_rounds_hint, gcd, limit_hint, SourceId, SourceTagged, Termination};
+use crate::Marker;
+
+mod fused;
+mod handle;
+
+use handle::{Command, Shared};
+pub use fused::FusedVecAltern;
+pub use handle::{PendingResult, VecAlternHandle};
+
+/// An iterator which can be cloned behind a `Box`, to mix iterators of different types in a `CloneVecAltern`.
+///
+/// It is implemented by all the iterators implementing `Clone`.
+pub trait CloneIterator<'a>: Iterator {
+    /// Clones the iterator into a new box.
+    fn clone_box(&self) -> Box<dyn CloneIterator<'a, Item = Self::Item> + 'a>;
+}
+
+impl<'a, I> CloneIterator<'a> for I
+where
+    I: Iterator + Clone + 'a
+{
+    fn clone_box(&self) -> Box<dyn CloneIterator<'a, Item = I::Item> + 'a> {
+        Box::new(self.clone())
+    }
+}
+
+impl<'a, A> Clone for Box<dyn CloneIterator<'a, Item = A> + 'a> {
+    fn clone(&self) -> Self {
+        (**self).clone_box()
+    }
+}
+
+/// A `VecAltern` over boxed iterators which can be cloned, so that it can be cloned too.
+///
+/// Build it with `CloneVecAltern::default()` and `add_cloneable`.
+pub type CloneVecAltern<'a, A> = VecAltern<'a, A, Box<dyn CloneIterator<'a, Item = A> + 'a>>;
+
+/// A `VecAltern` over boxed iterators which are `Send`, so that it is `Send` too, and can be moved to another thread.
+///
+/// Build it with `SendVecAltern::default()` and `add_send`, or with `altern!(send: ...)`.
+pub type SendVecAltern<'a, A> = VecAltern<'a, A, Box<dyn Iterator<Item = A> + Send + 'a>>;
+
+/// A `VecAltern` over boxed iterators which are `Send` and `Sync`, so that it is `Send` and `Sync` too.
+///
+/// Build it with `SyncVecAltern::default()` and `add_sync`.
+pub type SyncVecAltern<'a, A> = VecAltern<'a, A, Box<dyn Iterator<Item = A> + Send + Sync + 'a>>;
 
 /// Struct to altern between several iterator
-pub struct VecAltern<'a, A> {
-    iters: Vec<Box<dyn Iterator<Item = A> + 'a>>,
+///
+/// The iterators are boxed, unless they are of one concrete type `I` (see the module `altern`), in which case
+/// `VecAltern` also implements `ExactSizeIterator` and `DoubleEndedIterator` if `I` does.
+pub struct VecAltern<'a, A, I = Box<dyn Iterator<Item = A> + 'a>>
+where
+    I: Iterator<Item = A>
+{
+    slots: Vec<Slot<I>>,
     current: usize,
+    last: usize,
+    live: usize,
+    schedule: Option<Schedule>,
+    chunk: usize,
+    run: usize,
+    termination: Termination,
+    limit: Option<usize>,
+    /// The elements of the round in progress with `with_complete_rounds`, if the round is buffered.
+    round: Option<VecDeque<(usize, A)>>,
+    shared: Option<Arc<Shared<I>>>,
+    /// The element taken by `peek`, with the index of its iterator, if any.
+    peeked: Option<(usize, A)>,
+    _marker: Marker<'a, A>,
+}
+
+/// An iterator of a `VecAltern`, linked to the previous and next live iterators in the round-robin order.
+///
+/// Exhausted iterators are unlinked from the ring in constant time, and their slot is kept (empty) so that
+/// the remaining slots never move. Paused iterators are unlinked too, and kept in `paused`.
+#[derive(Clone)]
+struct Slot<I> {
+    iter: Option<I>,
+    paused: Option<I>,
+    weight: usize,
+    /// The number of turns taken in the period of the schedule.
+    taken: usize,
+    prev: usize,
+    next: usize,
+}
+
+/// The state of the smooth weighted round-robin order of the live slots, used instead of the ring as soon as an
+/// iterator has a weight other than 1.
+///
+/// In each period, a slot gets `weight / divisor` turns, its turn `k` being due at `(k + 1/2) / (weight / divisor)`
+/// of the period, and the turns due at the same time are taken in the order of the ring from `start`. Each slot
+/// counts its turns taken in the period, so that the next turn is found in a time linear in the number of live slots,
+/// and the position of any turn is computed without going through the period.
+#[derive(Clone, Copy)]
+struct Schedule {
+    /// The greatest common divisor of the weights of the live slots when the period started.
+    divisor: usize,
+    /// The number of turns in a period.
+    total: usize,
+    /// The number of turns taken in the period by the live slots.
+    taken: usize,
+    /// The slot whose turn is taken first among the turns due at the same time.
+    start: usize,
+}
+
+/// Returns how many of the `share` turns of a slot in a period are due before the turn `turn` of a slot of share
+/// `other`, the turns due at the same time counting if `first`.
+fn turns_before(share: usize, turn: usize, other: usize, first: bool) -> usize {
+    // The turn `k` is due before if `(2 * k + 1) * other < (2 * turn + 1) * share`.
+    let due = (2 * turn as u128 + 1) * share as u128;
+    let other = other as u128;
+    let before = ((due - 1) / other).div_ceil(2);
+    let tie = first && due.is_multiple_of(other) && (due / other) % 2 == 1;
+    (before + u128::from(tie)).min(share as u128) as usize
+}
+
+/// Returns whether the turn `turn` of a slot of share `share` is due before the turn `other_turn` of a slot of share
+/// `other`.
+fn due_before(turn: usize, share: usize, other_turn: usize, other: usize) -> bool {
+    (2 * turn as u128 + 1) * (other as u128) < (2 * other_turn as u128 + 1) * (share as u128)
+}
+
+/// An iterator which only has a size hint, standing for an iterator of a `VecAltern` in `pending_hint`.
+struct Hint<A> {
+    hint: (usize, Option<usize>),
+    _marker: PhantomData<fn() -> A>,
+}
+
+impl<A> Hint<A> {
+    fn new(hint: (usize, Option<usize>)) -> Self {
+        Self { hint, _marker: PhantomData }
+    }
+}
+
+impl<A> Iterator for Hint<A> {
+    type Item = A;
+
+    fn next(&mut self) -> Option<A> {
+        None
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.hint
+    }
 }
 
 impl<'a, A> VecAltern<'a, A> {
     /// Creates a new instance of an `Altern` iterator.
     pub fn new() -> Self {
-        Self {
-            iters: vec![],
-            current: 0,
-        }
+        Self::default()
     }
 
     /// Prepare the capacity, like `vec::with_capacity` does.
     pub fn with_capacity(capacity : usize) -> Self {
-        Self {
-            iters: Vec::with_capacity(capacity),
-            current: 0
-        }
+        Self::from(Vec::with_capacity(capacity))
     }
 
     /// Adds an iterator to the `Altern` instance.
@@ -76,7 +244,7 @@ impl<'a, A> VecAltern<'a, A> {
     ///
     /// The updated `Altern` instance with the added iterator, to use like a builder.
     pub fn add_and(mut self, iterator: impl Iterator<Item = A> + 'a) -> Self {
-        self.iters.push(Box::new(iterator));
+        self.push(Box::new(iterator));
         self
     }
 
@@ -87,83 +255,2037 @@ impl<'a, A> VecAltern<'a, A> {
     /// * `iterator` - An iterator of references to elements of type `A`.
     ///
     /// # Returns
-    pub fn add(&mut self, iterator: impl Iterator<Item = A> + 'a){
-        self.iters.push(Box::new(iterator));
+    ///
+    /// The id of the iterator, to pause, resume or remove it.
+    pub fn add(&mut self, iterator: impl Iterator<Item = A> + 'a) -> SourceId {
+        self.push(Box::new(iterator))
+    }
+
+    /// Adds an iterator to the `Altern` instance, which will yield `weight` elements each time the other
+    /// iterators yield theirs (see `push_weighted`).
+    ///
+    /// # Arguments
+    ///
+    /// * `iterator` - An iterator of references to elements of type `A`.
+    /// * `weight` - The number of elements to take from `iterator` in each period, at least 1.
+    pub fn add_weighted(&mut self, iterator: impl Iterator<Item = A> + 'a, weight: usize) -> SourceId {
+        self.push_weighted(Box::new(iterator), weight)
     }
 }
 
-impl<'a, A> Iterator for VecAltern<'a, A>
+impl<'a, A, I> VecAltern<'a, A, I>
+where
+    I: Iterator<Item = A>
 {
-    type Item = A;
+    /// Adds an iterator of the stored type, without boxing it.
+    ///
+    /// The iterator is inserted at the end of the round, after the last added iterator still running.
+    ///
+    /// # Arguments
+    ///
+    /// * `iterator` - An iterator of type `I`.
+    ///
+    /// # Returns
+    ///
+    /// The id of the iterator, to pause, resume or remove it.
+    pub fn push(&mut self, iterator: I) -> SourceId {
+        self.push_weighted(iterator, 1)
+    }
+
+    /// Adds an iterator of the stored type, without boxing it, which will yield `weight` elements each time the
+    /// other iterators yield theirs.
+    ///
+    /// As soon as an iterator has a weight other than 1, the iterators are visited in a smooth weighted round-robin
+    /// order: in each period of `sum of weights` elements, every iterator yields `weight` elements, spread as evenly
+    /// as possible (with weights 3 and 1, the order is `a a b a a a b a ...`).<br/>
+    /// The visits of an iterator of weight `w` fall at `1/2w`, `3/2w`, `5/2w`... of the period, and the visits
+    /// falling at the same time follow the order of the round. Finding the next iterator takes a time linear in the
+    /// number of iterators, whatever the weights.<br/>
+    /// Adding or resuming an iterator then starts a new period, the round starting at the iterator which would have
+    /// been visited next. When an iterator is exhausted, it is skipped, and the others keep their share of each period.
+    ///
+    /// # Arguments
+    ///
+    /// * `iterator` - An iterator of type `I`.
+    /// * `weight` - The number of elements to take from `iterator` in each period.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `weight` is 0.
+    ///
+    /// # Example
+    /// ```
+    /// use combin_iterator::altern::VecAltern;
+    /// let high = vec![1, 1, 1, 1, 1, 1, 1];
+    /// let low = vec![2, 2, 2];
+    ///
+    /// let mut iter = VecAltern::new();
+    /// iter.add_weighted(high.iter(), 3);
+    /// iter.add(low.iter());
+    /// assert_eq!(iter.collect::<Vec<_>>(), vec![&1, &1, &2, &1, &1, &1, &2, &1, &1, &2]);
+    /// ```
+    pub fn push_weighted(&mut self, iterator: I, weight: usize) -> SourceId {
+        assert!(weight > 0, "the weight of an iterator must be at least 1");
+        self.with_commands(|vec_altern| vec_altern.push_slot(iterator, weight))
+    }
 
+    /// Pauses an iterator: it is skipped until it is resumed.
+    ///
+    /// If it was the iterator to visit next, the following one is visited instead.
+    ///
+    /// # Returns
+    ///
+    /// Whether the iterator was running (neither paused, exhausted nor removed).
+    ///
+    /// # Example
+    /// ```
+    /// use combin_iterator::altern::VecAltern;
+    /// let vec1 = vec![1, 3, 5, 7];
+    /// let vec2 = vec![2, 4];
+    ///
+    /// let mut iter = VecAltern::new();
+    /// let first = iter.add(vec1.iter());
+    /// iter.add(vec2.iter());
+    ///
+    /// assert_eq!(iter.next(), Some(&1));
+    /// iter.pause(first);
+    /// assert_eq!(iter.next(), Some(&2));
+    /// assert_eq!(iter.next(), Some(&4));
+    /// iter.resume(first);
+    /// assert_eq!(iter.collect::<Vec<_>>(), vec![&3, &5, &7]);
+    /// ```
+    pub fn pause(&mut self, id: SourceId) -> bool {
+        self.with_commands(|vec_altern| vec_altern.pause_slot(id.index()))
+    }
 
-    /// Returns the next element in the iteration sequence.
+    /// Resumes a paused iterator. Like an added iterator, it is visited at the end of the round.
     ///
-    /// The `next` method alternates between the added iterators in a round-robin fashion.
-    fn next(&mut self) -> Option<A> {
-        loop {
-            if self.iters.is_empty() {
-                return None;
-            } else {
-                let next;
-                match self.iters.get_mut(self.current) {
-                    Some(iter) => {
-                        next = (*iter).next()
-                    },
+    /// # Returns
+    ///
+    /// Whether the iterator was paused.
+    pub fn resume(&mut self, id: SourceId) -> bool {
+        self.with_commands(|vec_altern| vec_altern.resume_slot(id.index()))
+    }
+
+    /// Removes and drops an iterator, running or paused.
+    ///
+    /// If it was the iterator to visit next, the following one is visited instead.
+    ///
+    /// # Returns
+    ///
+    /// Whether the iterator was running or paused.
+    pub fn remove(&mut self, id: SourceId) -> bool {
+        self.with_commands(|vec_altern| vec_altern.remove_source(id.index()))
+    }
+
+    /// Returns a handle to add, pause, resume or remove iterators while the `VecAltern` is consumed.
+    ///
+    /// # Example
+    /// ```
+    /// use combin_iterator::altern::VecAltern;
+    /// let vec1 = vec![1, 3, 5, 7];
+    /// let vec2 = vec![2, 4, 6, 8];
+    ///
+    /// let mut iter = VecAltern::new();
+    /// let first = iter.add(vec1.iter());
+    /// iter.add(vec2.iter());
+    /// let handle = iter.handle();
+    ///
+    /// let mut seen = vec![];
+    /// for value in iter {
+    ///     if *value == 3 {
+    ///         handle.remove(first);
+    ///     }
+    ///     seen.push(*value);
+    /// }
+    /// assert_eq!(seen, vec![1, 2, 3, 4, 6, 8]);
+    /// ```
+    pub fn handle(&mut self) -> VecAlternHandle<'a, A, I> {
+        let next_id = self.slots.len();
+        VecAlternHandle::new(self.shared.get_or_insert_with(|| Arc::new(Shared::new(next_id))).clone())
+    }
+
+    /// Returns a reference to the next element, without consuming it.
+    ///
+    /// The element is taken from its iterator and kept for the next call to `next`, even if its iterator is paused
+    /// or removed in between. The exhausted iterators found on the way are removed, like with `next`, so the element
+    /// comes from the first iterator still running.<br/>
+    /// When it returns `None`, nothing is kept: an iterator added or resumed afterwards is visited by `next`.
+    ///
+    /// # Example
+    /// ```
+    /// use combin_iterator::altern::VecAltern;
+    /// let vec1 = vec![1];
+    /// let vec2 = vec![2, 4];
+    /// let vec3 = vec![3, 5];
+    ///
+    /// let mut iter = VecAltern::new().add_and(vec1.iter()).add_and(vec2.iter()).add_and(vec3.iter());
+    /// assert_eq!(iter.by_ref().take(3).collect::<Vec<_>>(), vec![&1, &2, &3]);
+    /// // The first iterator is exhausted, so the next element comes from the second one.
+    /// assert_eq!(iter.peek(), Some(&&4));
+    /// assert_eq!(iter.peek_source(), Some(1));
+    /// assert_eq!(iter.collect::<Vec<_>>(), vec![&4, &5]);
+    /// ```
+    pub fn peek(&mut self) -> Option<&A> {
+        self.fill_peeked().map(|(_, value)| &*value)
+    }
+
+    /// Returns the index of the iterator the next element comes from, as tagged by `SourceTagged::next_tagged`,
+    /// without consuming it (see `peek`).
+    pub fn peek_source(&mut self) -> Option<usize> {
+        self.fill_peeked().map(|(source, _)| *source)
+    }
+
+    /// Returns the next element if `func` returns `true` for it, and keeps it for the next call otherwise.
+    ///
+    /// # Example
+    /// ```
+    /// use combin_iterator::altern::VecAltern;
+    /// let mut iter = VecAltern::from(vec![1..4, 10..13]);
+    /// let small = std::iter::from_fn(|| iter.next_if(|value| *value < 11)).collect::<Vec<_>>();
+    /// assert_eq!(small, vec![1, 10, 2]);
+    /// assert_eq!(iter.next(), Some(11));
+    /// ```
+    pub fn next_if(&mut self, func: impl FnOnce(&A) -> bool) -> Option<A> {
+        match self.next_tagged() {
+            Some((_, value)) if func(&value) => Some(value),
+            next => {
+                self.peeked = next;
+                None
+            },
+        }
+    }
+
+    /// Takes the next element with `peek`, if it was not already.
+    fn fill_peeked(&mut self) -> Option<&mut (usize, A)> {
+        if self.peeked.is_none() {
+            self.peeked = self.next_tagged();
+        }
+        self.peeked.as_mut()
+    }
+
+    /// Applies the commands sent by the handles, then calls `f`, without letting the handles send commands
+    /// in between, so that the ids they give stay in sync with the slots.
+    fn with_commands<R>(&mut self, f: impl FnOnce(&mut Self) -> R) -> R {
+        self.buffer_round();
+        let Some(shared) = self.shared.clone() else {
+            return f(self);
+        };
+        let mut commands = shared.lock();
+        shared.dirty.store(false, Ordering::Relaxed);
+        for command in commands.pending.drain(..) {
+            self.apply(command);
+        }
+        let result = f(self);
+        commands.next_id = self.slots.len();
+        result
+    }
+
+    /// Takes the rest of the round in progress with `with_complete_rounds`, and buffers the next rounds instead of
+    /// computing their number: it no longer holds once the iterators are changed.
+    fn buffer_round(&mut self) {
+        let Some(limit) = self.limit.take() else {
+            return;
+        };
+        let mut round = self.round.take().unwrap_or_default();
+        // `next_back` trims the iterators instead of counting its elements, so the limit gives the rest of the round.
+        for _ in 0..limit.checked_rem(self.period_len()).unwrap_or(0) {
+            match self.next_visited() {
+                Some(next) => round.push_back(next),
+                None => break,
+            }
+        }
+        self.round = Some(round);
+    }
+
+    /// Applies a command sent by a handle.
+    fn apply(&mut self, command: Command<I>) {
+        match command {
+            Command::Push(iterator, weight) => { self.push_slot(iterator, weight); },
+            Command::Pause(id, result) => result.set(self.pause_slot(id.index())),
+            Command::Resume(id, result) => result.set(self.resume_slot(id.index())),
+            Command::Remove(id, result) => result.set(self.remove_source(id.index())),
+        }
+    }
+
+    /// Applies the commands sent by the handles, if any.
+    fn sync(&mut self) {
+        if self.shared.as_ref().is_some_and(|shared| shared.dirty.load(Ordering::Acquire)) {
+            self.with_commands(|_| ());
+        }
+    }
+
+    /// Adds a slot for `iterator`, and links it.
+    fn push_slot(&mut self, iterator: I, weight: usize) -> SourceId {
+        let index = self.slots.len();
+        self.slots.push(Slot { iter: None, paused: None, weight, taken: 0, prev: index, next: index });
+        self.link(index, iterator);
+        SourceId(index)
+    }
+
+    /// Unlinks the running iterator of the slot `index`, and keeps it aside.
+    fn pause_slot(&mut self, index: usize) -> bool {
+        match self.slots.get_mut(index).and_then(|slot| slot.iter.take()) {
+            Some(iterator) => {
+                self.remove_slot(index);
+                self.slots[index].paused = Some(iterator);
+                true
+            },
+            None => false,
+        }
+    }
+
+    /// Links back the paused iterator of the slot `index`.
+    fn resume_slot(&mut self, index: usize) -> bool {
+        match self.slots.get_mut(index).and_then(|slot| slot.paused.take()) {
+            Some(iterator) => {
+                self.link(index, iterator);
+                true
+            },
+            None => false,
+        }
+    }
+
+    /// Drops the running or paused iterator of the slot `index`.
+    fn remove_source(&mut self, index: usize) -> bool {
+        match self.slots.get_mut(index) {
+            Some(slot) if slot.iter.is_some() => {
+                self.remove_slot(index);
+                true
+            },
+            Some(slot) => slot.paused.take().is_some(),
+            None => false,
+        }
+    }
+
+    /// Puts `iterator` in the unlinked slot `index`, and links it at the end of the round, after the last
+    /// linked slot.
+    fn link(&mut self, index: usize, iterator: I) {
+        let next = self.next_slot();
+        self.slots[index].iter = Some(iterator);
+        if self.live == 0 {
+            self.slots[index].prev = index;
+            self.slots[index].next = index;
+            self.current = index;
+        } else {
+            let first = self.slots[self.last].next;
+            self.slots[index].prev = self.last;
+            self.slots[index].next = first;
+            self.slots[self.last].next = index;
+            self.slots[first].prev = index;
+        }
+        self.last = index;
+        self.live += 1;
+
+        if self.slots[index].weight != 1 || self.schedule.is_some() {
+            // A new period starts after the chunk in progress, from the slot which would have been visited next.
+            let start = if self.run > 0 { self.slots[self.current].next } else { next.unwrap_or(index) };
+            let mut divisor = 0;
+            let mut total = 0;
+            let mut slot = start;
+            for _ in 0..self.live {
+                self.slots[slot].taken = 0;
+                divisor = gcd(divisor, self.slots[slot].weight);
+                total += self.slots[slot].weight;
+                slot = self.slots[slot].next;
+            }
+            self.schedule = Some(Schedule { divisor, total: total / divisor, taken: 0, start });
+        }
+    }
+
+    /// Sets how the alternation ends when an iterator is exhausted.
+    ///
+    /// With `Termination::Shortest`, it stops as soon as it is the turn of an exhausted iterator, and drops the
+    /// other iterators. Iterators added afterwards start a new alternation.
+    ///
+    /// # Example
+    /// ```
+    /// use combin_iterator::altern::{Termination, VecAltern};
+    /// let vec1 = vec![1, 4, 7];
+    /// let vec2 = vec![2, 5];
+    /// let vec3 = vec![3, 6, 8];
+    ///
+    /// let iter = VecAltern::new().add_and(vec1.iter()).add_and(vec2.iter()).add_and(vec3.iter())
+    ///     .with_termination(Termination::Shortest);
+    /// assert_eq!(iter.collect::<Vec<_>>(), vec![&1, &2, &3, &4, &5, &6, &7]);
+    /// ```
+    pub fn with_termination(mut self, termination: Termination) -> Self {
+        self.termination = termination;
+        self.limit = None;
+        self.round = None;
+        self
+    }
+
+    /// Stops the alternation like `Termination::Shortest`, but only at the end of a round: the elements of the last
+    /// round (one element of each iterator, or a period with weights) are dropped if an iterator is exhausted before
+    /// its end.
+    ///
+    /// The rounds start at the next element. If the size hints of the iterators are exact, like those of
+    /// `ExactSizeIterator`s, the number of elements yielded is computed from them. Otherwise, the elements of each
+    /// round are taken from the iterators when the round starts, and yielded once the round is complete.<br/>
+    /// An iterator added, paused, resumed or removed afterwards, directly or with a handle, changes the rounds
+    /// after the one in progress: the rest of this round is taken from the iterators, and the next rounds are taken
+    /// when they start.
+    ///
+    /// # Example
+    /// ```
+    /// use combin_iterator::altern::VecAltern;
+    /// let vec1 = vec![1, 4, 7];
+    /// let vec2 = vec![2, 5];
+    /// let vec3 = vec![3, 6, 8];
+    ///
+    /// let iter = VecAltern::from(vec![vec1.iter(), vec2.iter(), vec3.iter()]).with_complete_rounds();
+    /// assert_eq!(iter.collect::<Vec<_>>(), vec![&1, &2, &3, &4, &5, &6]);
+    /// ```
+    pub fn with_complete_rounds(self) -> Self {
+        let mut vec_altern = self.with_termination(Termination::Shortest);
+        match vec_altern.size_hint() {
+            (lower, Some(upper)) if lower == upper => {
+                let round = vec_altern.period_len();
+                vec_altern.limit = Some(lower.checked_div(round).map_or(0, |rounds| rounds * round));
+            },
+            _ => vec_altern.round = Some(VecDeque::new()),
+        }
+        vec_altern
+    }
+
+    /// Takes up to `chunk` consecutive elements from each iterator before moving to the next one.
+    ///
+    /// When an iterator has fewer elements left than `chunk`, they are all yielded as a shorter chunk, and the next
+    /// iterator starts its chunk. With `Termination::Shortest`, the alternation stops after the shorter chunk instead,
+    /// and `with_complete_rounds` drops the whole round. With weights, each turn of the period is a chunk (see
+    /// `push_weighted`).<br/>
+    /// A chunk in progress ends, and the next element starts a new chunk.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `chunk` is 0.
+    ///
+    /// # Example
+    /// ```
+    /// use combin_iterator::altern::VecAltern;
+    /// let vec1 = vec![1, 1, 1, 1, 1];
+    /// let vec2 = vec![2, 2];
+    /// let vec3 = vec![3, 3, 3, 3];
+    ///
+    /// let iter = VecAltern::from(vec![vec1.iter(), vec2.iter(), vec3.iter()]).with_chunk(2);
+    /// assert_eq!(iter.collect::<Vec<_>>(), vec![&1, &1, &2, &2, &3, &3, &1, &1, &3, &3, &1]);
+    /// ```
+    pub fn with_chunk(mut self, chunk: usize) -> Self {
+        assert!(chunk > 0, "the chunk of an iterator must be at least 1");
+        if self.run > 0 && self.schedule.is_none() && self.live > 0 {
+            self.current = self.slots[self.current].next;
+        }
+        self.chunk = chunk;
+        self.run = 0;
+        self
+    }
+
+    /// Returns the slot which will be visited next.
+    fn next_slot(&self) -> Option<usize> {
+        match self.schedule.as_ref() {
+            _ if self.live == 0 => None,
+            _ if self.run > 0 => Some(self.current),
+            None => Some(self.current),
+            Some(schedule) => self.scheduled_slot(schedule),
+        }
+    }
+
+    /// Returns the slot of the next turn of the schedule: the live slot whose next turn is due first.
+    fn scheduled_slot(&self, schedule: &Schedule) -> Option<usize> {
+        let restart = schedule.taken == schedule.total;
+        let mut first: Option<(usize, usize, usize)> = None;
+        for index in self.live_slots_from(schedule.start) {
+            let share = self.slots[index].weight / schedule.divisor;
+            let turn = if restart { 0 } else { self.slots[index].taken };
+            if turn < share && first.is_none_or(|(_, first_turn, first_share)| due_before(turn, share, first_turn, first_share)) {
+                first = Some((index, turn, share));
+            }
+        }
+        first.map(|(index, _, _)| index)
+    }
+
+    /// Takes the next turn of the schedule, starting a new period if the current one is over, and returns its slot.
+    fn take_turn(&mut self) -> usize {
+        let Some(mut schedule) = self.schedule else { return self.current };
+        if schedule.taken == schedule.total {
+            let mut slot = schedule.start;
+            for _ in 0..self.live {
+                self.slots[slot].taken = 0;
+                slot = self.slots[slot].next;
+            }
+            schedule.taken = 0;
+        }
+        let index = self.scheduled_slot(&schedule).expect("a live slot has a turn left in the period");
+        self.slots[index].taken += 1;
+        schedule.taken += 1;
+        self.schedule = Some(schedule);
+        index
+    }
+
+    /// Returns the position, among the next turns of the schedule, of the turn of the live slot `index` which comes
+    /// after `turns` of its turns, or `None` if it overflows.
+    fn turn_position(&self, schedule: &Schedule, index: usize, turns: usize) -> Option<usize> {
+        let share = self.slots[index].weight / schedule.divisor;
+        let (taken, taken_all) = match schedule.taken == schedule.total {
+            true => (0, 0),
+            false => (self.slots[index].taken, schedule.taken),
+        };
+        let (offset, turn) = match turns.checked_sub(share - taken) {
+            None => (None, taken + turns),
+            Some(turns) => (Some((turns / share).checked_mul(schedule.total)?.checked_add(schedule.total - taken_all)?), turns % share),
+        };
+        // The turns due before it in a period, in the order of the ring from `start`.
+        let mut first = true;
+        let mut place = 0;
+        for other in self.live_slots_from(schedule.start) {
+            first &= other != index;
+            place += turns_before(self.slots[other].weight / schedule.divisor, turn, share, first);
+        }
+        match offset {
+            None => Some(place - taken_all),
+            Some(offset) => offset.checked_add(place),
+        }
+    }
+
+    /// Returns the slot to visit, and moves to the visit after it: the same slot until the end of its chunk.
+    ///
+    /// Without weights, `current` is the slot to visit next; with weights, it is the slot of the current chunk.
+    fn visit_slot(&mut self) -> usize {
+        if self.run == 0 {
+            self.run = self.chunk;
+            if self.schedule.is_some() {
+                self.current = self.take_turn();
+            }
+        }
+        self.run -= 1;
+        let index = self.current;
+        if self.run == 0 && self.schedule.is_none() {
+            self.current = self.slots[index].next;
+        }
+        index
+    }
+
+    /// Unlinks the iterator of the slot `index` from the ring, and drops it.
+    ///
+    /// If it was the current iterator, the next one becomes the current.
+    fn remove_slot(&mut self, index: usize) {
+        let Slot { weight, taken, prev, next, .. } = self.slots[index];
+        self.slots[index].iter = None;
+        self.slots[prev].next = next;
+        self.slots[next].prev = prev;
+        self.live -= 1;
+        if self.current == index {
+            self.current = next;
+            self.run = 0;
+        }
+        if self.last == index {
+            self.last = prev;
+        }
+        if let Some(schedule) = self.schedule.as_mut() {
+            schedule.total -= weight / schedule.divisor;
+            schedule.taken -= taken;
+            if schedule.start == index {
+                schedule.start = next;
+            }
+        }
+    }
+
+    /// Drops all the iterators, to end the alternation with `Termination::Shortest`.
+    fn stop(&mut self) {
+        while self.live > 0 {
+            self.remove_slot(self.current);
+        }
+    }
+
+    /// Returns the next element of the round in progress, taking the next round from the iterators once it is over,
+    /// and dropping it if an iterator is exhausted before its end.
+    fn next_buffered(&mut self) -> Option<(usize, A)> {
+        let mut round = self.round.take()?;
+        if round.is_empty() {
+            for _ in 0..self.period_len() {
+                match self.next_visited() {
+                    Some(next) => round.push_back(next),
                     None => {
-                        panic!("altern.current out of bound for altern.iters ")
+                        round.clear();
+                        break;
                     },
                 }
+            }
+        }
+        let next = round.pop_front();
+        self.round = Some(round);
+        next
+    }
 
-                match next {
-                    Some(value) => {
-                        self.current = (self.current + 1) % self.iters.len();
-                        return Some(value)
-                    },
-                    None => {
-                        let _ = self.iters.remove(self.current);
-                        let n = self.iters.len().max(1);
-                        self.current = self.current % n;
+    /// Returns the next element from the iterators, with the index of its iterator.
+    fn next_visited(&mut self) -> Option<(usize, A)> {
+        if self.limit == Some(0) {
+            return None;
+        }
+        while self.live > 0 {
+            let index = self.visit_slot();
+            let next = match self.slots[index].iter.as_mut() {
+                Some(iter) => iter.next(),
+                None => panic!("altern.current points to an exhausted iterator"),
+            };
+
+            match next {
+                Some(value) => {
+                    if let Some(limit) = self.limit.as_mut() {
+                        *limit -= 1;
                     }
-                }
+                    return Some((index, value))
+                },
+                None if self.termination == Termination::Shortest => self.stop(),
+                None => self.remove_slot(index),
             }
         }
+        None
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use super::VecAltern;
+    /// Returns the number of visits of the slot `index` in a period.
+    fn turns(&self, index: usize) -> usize {
+        self.chunk * self.schedule.as_ref().map_or(1, |schedule| self.slots[index].weight / schedule.divisor)
+    }
 
-    #[test]
-    fn vec_altern() {
-        let vec1 = vec![1, 4, 7, 9];
-        let vec2 = vec![2, 5];
-        let vec3 = vec![3, 6, 8];
+    /// Returns the number of visits in a period: the number of live slots, or the live turns of the schedule, times
+    /// the chunk.
+    fn period_len(&self) -> usize {
+        self.chunk * self.schedule.as_ref().map_or(self.live, |schedule| schedule.total)
+    }
 
-        let iter = VecAltern::new().add_and(vec1.iter()).add_and(vec2.iter()).add_and(vec3.iter());
+    /// Returns the position, among the next visits, of the visit of the live slot `index` which comes after `visits`
+    /// of its visits, or `None` if it overflows.
+    ///
+    /// The rest of the chunk in progress comes first, then the chunks of each turn.
+    fn visit_position(&self, index: usize, visits: usize) -> Option<usize> {
+        let visits = match index == self.current {
+            true if visits < self.run => return Some(visits),
+            true => visits - self.run,
+            false => visits,
+        };
+        let turn = match self.schedule.as_ref() {
+            None => {
+                let start = if self.run > 0 { self.slots[self.current].next } else { self.current };
+                let place = self.live_slots_from(start).position(|slot| slot == index)?;
+                (visits / self.chunk).checked_mul(self.live)?.checked_add(place)?
+            },
+            Some(schedule) => self.turn_position(schedule, index, visits / self.chunk)?,
+        };
+        turn.checked_mul(self.chunk)?.checked_add(visits % self.chunk)?.checked_add(self.run)
+    }
 
-        assert_eq!(iter.collect::<Vec<_>>(), vec![&1,&2,&3,&4,&5,&6,&7,&8, &9]);
+    /// Returns the number of visits of the live slot `index` among the next `len` visits.
+    fn visits(&self, index: usize, len: usize) -> usize {
+        // The positions of the visits increase, so the first one at `len` or after is found by bisection.
+        let (mut low, mut high) = (0, len);
+        while low < high {
+            let middle = low + (high - low) / 2;
+            match self.visit_position(index, middle) {
+                Some(position) if position < len => low = middle + 1,
+                _ => high = middle,
+            }
+        }
+        low
     }
 
-    #[test]
-    fn empty_vec_altern() {
-        let vec1 : Vec<u8> = vec![];
-        let vec2: Vec<u8> = vec![];
-        let vec3: Vec<u8> = vec![];
+    /// Calls `f` with each live slot for which `visits` returns a number of visits, and the position among the next
+    /// visits of the visit of this slot which comes after them (or `None` if it overflows).
+    fn visit_positions(&self, visits: impl Fn(usize) -> Option<usize>, mut f: impl FnMut(usize, Option<usize>)) {
+        for index in self.live_slots() {
+            if let Some(visits) = visits(index) {
+                f(index, self.visit_position(index, visits));
+            }
+        }
+    }
 
-        let mut iter = VecAltern::new().add_and(vec1.iter()).add_and(vec2.iter()).add_and(vec3.iter());
+    /// Returns the number of elements yielded with `Termination::Shortest` by iterators of `lens(slot)` elements
+    /// (`None` for infinite iterators), or `None` if it is infinite.
+    fn shortest_len(&self, lens: impl Fn(usize) -> Option<usize>) -> Option<usize> {
+        let mut len: Option<usize> = None;
+        self.visit_positions(lens, |_, position| {
+            if let Some(position) = position {
+                len = Some(len.map_or(position, |len| len.min(position)));
+            }
+        });
+        len
+    }
 
-        assert_eq!(iter.next(), None);
+    /// Returns whether the iteration can be done in bulk: with `Termination::Longest`, without a limit, and without
+    /// handles, whose commands must be applied before each element.
+    fn can_iterate_in_bulk(&self) -> bool {
+        self.termination == Termination::Longest && self.limit.is_none() && self.shared.is_none()
     }
 
-    use crate::altern;
-    #[test]
-    fn macro_altern() {
-        let vec1 = vec![1, 4, 7, 9];
-        let vec2 = vec![2, 5];
-        let vec3 = vec![3, 6, 8];
-        let iter = altern!(vec1.iter(), vec2.iter(), vec3.iter());
-        assert_eq!(iter.collect::<Vec<_>>(), vec![&1,&2,&3,&4,&5,&6,&7,&8, &9]);
+    /// Folds at most `steps` elements with `Termination::Longest` while several iterators are running, without
+    /// the checks of `next`.
+    fn fold_rounds<B>(&mut self, init: B, mut f: impl FnMut(B, A) -> B, steps: usize) -> B {
+        let mut acc = init;
+        for _ in 0..steps {
+            if self.live <= 1 {
+                break;
+            }
+            let index = self.visit_slot();
+            match self.slots[index].iter.as_mut().and_then(Iterator::next) {
+                Some(value) => acc = f(acc, value),
+                None => self.remove_slot(index),
+            }
+        }
+        acc
+    }
+
+    /// Returns the number of whole periods which can be done before an iterator is exhausted, according to the
+    /// lower bounds of their size hints.
+    fn safe_periods(&self) -> usize {
+        self.live_slots().map(|index| self.slot_hint(index).0 / self.turns(index)).min().unwrap_or(0)
+    }
+
+    /// Returns how many elements to step through before calling `safe_periods` again, after skipping `periods`
+    /// periods and stepping through `steps` elements the previous time.
+    ///
+    /// Stepping through a period is enough to reach the next exhausted iterator, and the steps double while nothing
+    /// can be skipped, so that `safe_periods` takes a constant time per element.
+    fn next_steps(&self, periods: usize, steps: usize) -> usize {
+        if periods > 0 { self.period_len() } else { steps.max(self.period_len()).saturating_mul(2) }
+    }
+
+    /// Skips `periods` whole periods, at most `safe_periods()`, with `nth` on each iterator.
+    ///
+    /// A whole period leaves the current iterator and the schedule unchanged, so the alternation goes on as if
+    /// the elements were yielded.
+    fn skip_periods(&mut self, periods: usize) {
+        if periods > 0 {
+            // Walks the ring of the live slots, which `nth` leaves unchanged.
+            let mut index = self.current;
+            for _ in 0..self.live {
+                let turns = self.turns(index);
+                if let Some(iter) = self.slots[index].iter.as_mut() {
+                    iter.nth(periods * turns - 1);
+                }
+                index = self.slots[index].next;
+            }
+        }
     }
-}
\ No newline at end of file
+
+    /// Returns the iterator of the last running slot, if only one is left.
+    fn last_source(&mut self) -> Option<&mut I> {
+        match self.live {
+            1 => self.slots[self.current].iter.as_mut(),
+            _ => None,
+        }
+    }
+
+    /// Returns the size hint of the iterator of the slot `index`.
+    fn slot_hint(&self, index: usize) -> (usize, Option<usize>) {
+        self.slots[index].iter.as_ref().map_or((0, Some(0)), |iter| iter.size_hint())
+    }
+
+    /// Returns the indexes of the live slots, in the order they will be visited.
+    fn live_slots(&self) -> impl Iterator<Item = usize> + '_ {
+        self.live_slots_from(self.current)
+    }
+
+    /// Returns the indexes of the live slots, in the order of the ring, starting at the live slot `start`.
+    fn live_slots_from(&self, start: usize) -> impl Iterator<Item = usize> + '_ {
+        let mut index = start;
+        (0..self.live).map(move |_| {
+            let slot = index;
+            index = self.slots[slot].next;
+            slot
+        })
+    }
+
+    /// Returns the size hint of the elements left to take from the iterators.
+    fn iters_hint(&self) -> (usize, Option<usize>) {
+        let hint = match self.termination {
+            Termination::Shortest if self.live > 0 => {
+                let lower = self.shortest_len(|index| Some(self.slot_hint(index).0)).unwrap_or(usize::MAX);
+                (lower, self.shortest_len(|index| self.slot_hint(index).1))
+            },
+            _ => self.live_slots().fold((0usize, Some(0usize)), |(lower, upper), index| {
+                let hint = self.slot_hint(index);
+                (lower.saturating_add(hint.0), upper.zip(hint.1).and_then(|(u1, u2)| u1.checked_add(u2)))
+            }),
+        };
+        let hint = match self.round {
+            Some(_) => complete_rounds_hint(hint, self.period_len(), 0),
+            None => hint,
+        };
+        limit_hint(hint, self.limit)
+    }
+
+    /// Returns the size hint of the elements left to take from the iterators once the commands sent by the handles
+    /// are applied, if there are any.
+    ///
+    /// The commands are applied to a copy of the alternation which holds the size hints of the iterators instead of
+    /// the iterators.
+    fn pending_hint(&self) -> Option<(usize, Option<usize>)> {
+        let shared = self.shared.as_ref().filter(|shared| shared.dirty.load(Ordering::Acquire))?;
+        let commands = shared.lock();
+        if commands.pending.is_empty() {
+            return None;
+        }
+        let hint = |iter: &I| Hint::new(iter.size_hint());
+        let mut copy = VecAltern::<A, Hint<A>> {
+            slots: self.slots.iter().map(|slot| Slot {
+                iter: slot.iter.as_ref().map(hint),
+                paused: slot.paused.as_ref().map(hint),
+                weight: slot.weight,
+                taken: slot.taken,
+                prev: slot.prev,
+                next: slot.next,
+            }).collect(),
+            current: self.current,
+            last: self.last,
+            live: self.live,
+            schedule: self.schedule,
+            chunk: self.chunk,
+            run: self.run,
+            termination: self.termination,
+            limit: None,
+            round: (self.round.is_some() || self.limit.is_some()).then(VecDeque::new),
+            shared: None,
+            peeked: None,
+            _marker: PhantomData,
+        };
+        // Like `buffer_round`, the commands take the rest of the round in progress first.
+        let rest = self.limit.and_then(|limit| limit.checked_rem(self.period_len())).unwrap_or(0);
+        for _ in 0..rest {
+            let index = copy.visit_slot();
+            if let Some(iter) = copy.slots[index].iter.as_mut() {
+                iter.hint = (iter.hint.0.saturating_sub(1), iter.hint.1.map(|upper| upper.saturating_sub(1)));
+            }
+        }
+        for command in commands.pending.iter() {
+            copy.apply(command.map(hint));
+        }
+        let (lower, upper) = copy.iters_hint();
+        Some((lower.saturating_add(rest), upper.and_then(|upper| upper.checked_add(rest))))
+    }
+}
+
+impl<'a, A, I> VecAltern<'a, A, I>
+where
+    I: DoubleEndedIterator<Item = A> + ExactSizeIterator
+{
+    /// Drops from the back of the iterators the elements which will not be yielded with `Termination::Shortest`.
+    fn trim(&mut self) {
+        let len = self.shortest_len(|index| self.slots[index].iter.as_ref().map(|iter| iter.len()));
+        if let (Some(len), Some(_), None) = (len, &self.round, self.limit) {
+            // The rounds left are complete once the iterators are trimmed, and stay so as the last elements are taken.
+            self.limit = Some(complete_rounds_hint((len, None), self.period_len(), 0).0);
+        }
+        let len = match (len, self.limit) {
+            (Some(len), Some(limit)) => len.min(limit),
+            (Some(len), None) | (None, Some(len)) => len,
+            (None, None) => return,
+        };
+        let mut index = self.current;
+        for _ in 0..self.live {
+            let visits = self.visits(index, len);
+            if let Some(iter) = self.slots[index].iter.as_mut() {
+                if iter.len() > visits {
+                    iter.nth_back(iter.len() - visits - 1);
+                }
+            }
+            index = self.slots[index].next;
+        }
+    }
+
+    /// Returns the last element, ignoring the peeked element.
+    fn next_back_unpeeked(&mut self) -> Option<A> {
+        self.sync();
+        if self.limit == Some(0) {
+            return None;
+        }
+        if self.termination == Termination::Shortest && self.live > 0 {
+            self.trim();
+        }
+
+        let mut last = None;
+        let mut max_position = 0;
+        let last_visits = |index: usize| match self.slots[index].iter.as_ref().map(|iter| iter.len()) {
+            Some(len) if len > 0 => Some(len - 1),
+            _ => None,
+        };
+        self.visit_positions(last_visits, |index, position| {
+            let position = position.unwrap_or(usize::MAX);
+            if last.is_none() || position > max_position {
+                max_position = position;
+                last = Some(index);
+            }
+        });
+
+        let next = last.and_then(|index| self.slots[index].iter.as_mut()?.next_back());
+        // The buffered elements of the round in progress come before those left in the iterators.
+        next.or_else(|| self.round.as_mut()?.pop_back().map(|(_, value)| value))
+    }
+}
+
+impl<'a, A> CloneVecAltern<'a, A> {
+    /// Adds an iterator which can be cloned, boxed so that it can be mixed with iterators of other types.
+    ///
+    /// # Returns
+    ///
+    /// The id of the iterator, to pause, resume or remove it.
+    ///
+    /// # Example
+    /// ```
+    /// use combin_iterator::altern::CloneVecAltern;
+    /// let vec1 = vec![1, 3, 5];
+    ///
+    /// let mut iter = CloneVecAltern::default();
+    /// iter.add_cloneable(vec1.iter().copied());
+    /// iter.add_cloneable((2..5).step_by(2));
+    /// assert_eq!(iter.next(), Some(1));
+    ///
+    /// let replay = iter.clone();
+    /// assert_eq!(iter.collect::<Vec<_>>(), vec![2, 3, 4, 5]);
+    /// assert_eq!(replay.collect::<Vec<_>>(), vec![2, 3, 4, 5]);
+    /// ```
+    pub fn add_cloneable(&mut self, iterator: impl Iterator<Item = A> + Clone + 'a) -> SourceId {
+        self.push(Box::new(iterator))
+    }
+
+    /// Adds an iterator which can be cloned, like `add_cloneable`.
+    ///
+    /// # Returns
+    ///
+    /// The updated `CloneVecAltern` instance with the added iterator, to use like a builder.
+    pub fn add_cloneable_and(mut self, iterator: impl Iterator<Item = A> + Clone + 'a) -> Self {
+        self.push(Box::new(iterator));
+        self
+    }
+}
+
+impl<'a, A> SendVecAltern<'a, A> {
+    /// Adds an iterator which is `Send`, boxed so that it can be mixed with iterators of other types.
+    ///
+    /// # Returns
+    ///
+    /// The id of the iterator, to pause, resume or remove it.
+    ///
+    /// # Example
+    /// ```
+    /// use combin_iterator::altern::SendVecAltern;
+    /// let mut iter = SendVecAltern::default();
+    /// iter.add_send(vec![1, 3, 5].into_iter());
+    /// iter.add_send((2..5).step_by(2));
+    ///
+    /// let worker = std::thread::spawn(move || iter.collect::<Vec<_>>());
+    /// assert_eq!(worker.join().unwrap(), vec![1, 2, 3, 4, 5]);
+    /// ```
+    pub fn add_send(&mut self, iterator: impl Iterator<Item = A> + Send + 'a) -> SourceId {
+        self.push(Box::new(iterator))
+    }
+
+    /// Adds an iterator which is `Send`, like `add_send`.
+    ///
+    /// # Returns
+    ///
+    /// The updated `SendVecAltern` instance with the added iterator, to use like a builder.
+    pub fn add_send_and(mut self, iterator: impl Iterator<Item = A> + Send + 'a) -> Self {
+        self.push(Box::new(iterator));
+        self
+    }
+}
+
+impl<'a, A> SyncVecAltern<'a, A> {
+    /// Adds an iterator which is `Send` and `Sync`, boxed so that it can be mixed with iterators of other types.
+    ///
+    /// # Returns
+    ///
+    /// The id of the iterator, to pause, resume or remove it.
+    pub fn add_sync(&mut self, iterator: impl Iterator<Item = A> + Send + Sync + 'a) -> SourceId {
+        self.push(Box::new(iterator))
+    }
+
+    /// Adds an iterator which is `Send` and `Sync`, like `add_sync`.
+    ///
+    /// # Returns
+    ///
+    /// The updated `SyncVecAltern` instance with the added iterator, to use like a builder.
+    pub fn add_sync_and(mut self, iterator: impl Iterator<Item = A> + Send + Sync + 'a) -> Self {
+        self.push(Box::new(iterator));
+        self
+    }
+}
+
+impl<'a, A, I> Clone for VecAltern<'a, A, I>
+where
+    A: Clone,
+    I: Iterator<Item = A> + Clone
+{
+    /// Clones the iterators and the position in the alternation, to replay it.
+    ///
+    /// The clone is not linked to the handles (see `handle`), but the changes they requested before are applied to
+    /// it, like they will be to the original.
+    fn clone(&self) -> Self {
+        let mut clone = Self {
+            slots: self.slots.clone(),
+            current: self.current,
+            last: self.last,
+            live: self.live,
+            schedule: self.schedule,
+            chunk: self.chunk,
+            run: self.run,
+            termination: self.termination,
+            limit: self.limit,
+            round: self.round.clone(),
+            shared: None,
+            peeked: self.peeked.clone(),
+            _marker: PhantomData,
+        };
+        if let Some(shared) = self.shared.as_ref() {
+            for command in shared.lock().pending.iter() {
+                clone.apply(command.map(I::clone));
+            }
+        }
+        clone
+    }
+}
+
+impl<'a, A, I> fmt::Debug for VecAltern<'a, A, I>
+where
+    I: Iterator<Item = A>
+{
+    /// Shows the indexes of the running iterators, in the order of the round from the one visited next, of the
+    /// paused iterators, and the settings of the alternation, without requiring the iterators to implement `Debug`.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let next = self.next_slot();
+        let sources = next.map_or_else(Vec::new, |next| self.live_slots_from(next).collect());
+        let paused = (0..self.slots.len()).filter(|&index| self.slots[index].paused.is_some()).collect::<Vec<_>>();
+        f.debug_struct("VecAltern")
+            .field("sources", &sources)
+            .field("paused", &paused)
+            .field("next", &next)
+            .field("chunk", &self.chunk)
+            .field("termination", &self.termination)
+            .field("limit", &self.limit)
+            .field("peeked_source", &self.peeked.as_ref().map(|(source, _)| *source))
+            .finish()
+    }
+}
+
+impl<'a, A, I> Default for VecAltern<'a, A, I>
+where
+    I: Iterator<Item = A>
+{
+    fn default() -> Self {
+        Self::from(Vec::new())
+    }
+}
+
+impl<'a, A, I> From<Vec<I>> for VecAltern<'a, A, I>
+where
+    I: Iterator<Item = A>
+{
+    /// Creates a `VecAltern` over iterators of one concrete type, without boxing them.
+    ///
+    /// ```
+    /// use combin_iterator::altern::VecAltern;
+    /// let vec1 = vec![1, 4, 7, 9];
+    /// let vec2 = vec![2, 5];
+    /// let vec3 = vec![3, 6, 8];
+    ///
+    /// let iter = VecAltern::from(vec![vec1.iter(), vec2.iter(), vec3.iter()]);
+    /// assert_eq!(iter.len(), 9);
+    /// assert_eq!(iter.rev().collect::<Vec<_>>(), vec![&9, &8, &7, &6, &5, &4, &3, &2, &1]);
+    /// ```
+    fn from(iters: Vec<I>) -> Self {
+        let mut vec_altern = Self {
+            slots: Vec::with_capacity(iters.capacity()),
+            current: 0,
+            last: 0,
+            live: 0,
+            schedule: None,
+            chunk: 1,
+            run: 0,
+            termination: Termination::Longest,
+            limit: None,
+            round: None,
+            shared: None,
+            peeked: None,
+            _marker: PhantomData,
+        };
+        for iter in iters {
+            vec_altern.push(iter);
+        }
+        vec_altern
+    }
+}
+
+impl<'a, A, J> FromIterator<J> for VecAltern<'a, A>
+where
+    J: IntoIterator<Item = A>,
+    J::IntoIter: 'a
+{
+    /// Creates a `VecAltern` over the collected iterators, boxed like with `add`.
+    ///
+    /// ```
+    /// use combin_iterator::altern::VecAltern;
+    /// let lines = vec![vec![1, 4, 7], vec![2, 5], vec![3, 6]];
+    ///
+    /// let iter = lines.iter().map(|line| line.iter()).collect::<VecAltern<_>>();
+    /// assert_eq!(iter.collect::<Vec<_>>(), vec![&1, &2, &3, &4, &5, &6, &7]);
+    /// ```
+    fn from_iter<T: IntoIterator<Item = J>>(iters: T) -> Self {
+        let iters = iters.into_iter();
+        let mut vec_altern = Self::with_capacity(iters.size_hint().0);
+        for iter in iters {
+            vec_altern.add(iter.into_iter());
+        }
+        vec_altern
+    }
+}
+
+impl<'a, A, I> Iterator for VecAltern<'a, A, I>
+where
+    I: Iterator<Item = A>
+{
+    type Item = A;
+
+
+    /// Returns the next element in the iteration sequence.
+    ///
+    /// The `next` method alternates between the added iterators in a round-robin fashion.
+    fn next(&mut self) -> Option<A> {
+        self.next_tagged().map(|(_, value)| value)
+    }
+
+    // `try_fold` keeps its default implementation, based on `next`: it cannot be overridden on stable Rust,
+    // because its signature uses the unstable `Try` trait.
+
+    /// Folds the elements without the checks of `next`, then folding the last running iterator with its own
+    /// `fold` once the others are exhausted.
+    fn fold<B, F>(mut self, init: B, mut f: F) -> B
+    where
+        F: FnMut(B, A) -> B
+    {
+        let init = match self.peeked.take() {
+            Some((_, value)) => f(init, value),
+            None => init,
+        };
+        if !self.can_iterate_in_bulk() {
+            let mut acc = init;
+            for value in self.by_ref() {
+                acc = f(acc, value);
+            }
+            return acc;
+        }
+
+        let acc = self.fold_rounds(init, &mut f, usize::MAX);
+        match self.last_source() {
+            Some(iter) => iter.fold(acc, f),
+            None => acc,
+        }
+    }
+
+    /// Returns the `n`th element, first skipping whole periods with `nth` on every iterator as long as their size
+    /// hints guarantee that they are not exhausted, which takes a time linear in the number of iterators for
+    /// iterators like ranges or slices.
+    fn nth(&mut self, mut n: usize) -> Option<A> {
+        if let Some((_, value)) = self.peeked.take() {
+            if n == 0 {
+                return Some(value);
+            }
+            n -= 1;
+        }
+        if !self.can_iterate_in_bulk() {
+            for _ in 0..n {
+                self.next()?;
+            }
+            return self.next();
+        }
+
+        let mut steps = 0;
+        while self.live > 1 {
+            let period = self.period_len();
+            let periods = (n / period).min(self.safe_periods());
+            self.skip_periods(periods);
+            n -= periods * period;
+            steps = self.next_steps(periods, steps);
+            for _ in 0..steps {
+                if n == 0 || self.live <= 1 {
+                    break;
+                }
+                self.next()?;
+                n -= 1;
+            }
+            if n == 0 {
+                return self.next();
+            }
+        }
+        self.last_source()?.nth(n)
+    }
+
+    /// Counts the elements, skipping whole periods like `nth`, then counting the last running iterator with its
+    /// own `count` once the others are exhausted.
+    fn count(mut self) -> usize {
+        let mut count = usize::from(self.peeked.take().is_some());
+        if !self.can_iterate_in_bulk() {
+            return self.fold(count, |count, _| count + 1);
+        }
+
+        let mut steps = 0;
+        while self.live > 1 {
+            let periods = self.safe_periods();
+            self.skip_periods(periods);
+            count += periods * self.period_len();
+            steps = self.next_steps(periods, steps);
+            count = self.fold_rounds(count, |count, _| count + 1, steps);
+        }
+        count + self.last_source().map_or(0, |iter| iter.count())
+    }
+
+    /// Returns the sum of the bounds of the remaining iterators.
+    ///
+    /// The lower bound saturates at `usize::MAX`, and the upper bound is `None` if it overflows. The changes requested
+    /// by the handles and not applied yet are taken into account.
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (lower, upper) = self.pending_hint().unwrap_or_else(|| self.iters_hint());
+        let taken = self.round.as_ref().map_or(0, VecDeque::len) + usize::from(self.peeked.is_some());
+        (lower.saturating_add(taken), upper.and_then(|upper| upper.checked_add(taken)))
+    }
+}
+
+impl<'a, A, I> SourceTagged for VecAltern<'a, A, I>
+where
+    I: Iterator<Item = A>
+{
+    /// Returns the number of iterators added, including the exhausted ones.
+    fn source_count(&self) -> usize {
+        self.slots.len()
+    }
+
+    /// Returns the next element, with the index of its iterator in the order they were added.
+    fn next_tagged(&mut self) -> Option<(usize, A)> {
+        if let Some(peeked) = self.peeked.take() {
+            return Some(peeked);
+        }
+        self.sync();
+        match self.round.is_some() {
+            true => self.next_buffered(),
+            false => self.next_visited(),
+        }
+    }
+}
+
+impl<'a, A, I> DoubleEndedIterator for VecAltern<'a, A, I>
+where
+    I: DoubleEndedIterator<Item = A> + ExactSizeIterator,
+{
+    /// Returns the last element of the iteration sequence.
+    ///
+    /// The last element comes from the longest iterator, and if several are as long, from the one
+    /// visited last in the round starting at the current iterator.<br/>
+    /// With weights, the last element comes from the iterator whose last element is the furthest in the period
+    /// order. The element taken by `peek` comes last, once the iterators are exhausted.
+    fn next_back(&mut self) -> Option<A> {
+        self.next_back_unpeeked().or_else(|| self.peeked.take().map(|(_, value)| value))
+    }
+}
+
+impl<'a, A, I> ExactSizeIterator for VecAltern<'a, A, I>
+where
+    I: ExactSizeIterator<Item = A> {}
+
+#[cfg(test)]
+// The original tests build their iterators from vectors.
+#[allow(clippy::useless_vec)]
+mod tests {
+    use super::VecAltern;
+    use crate::altern::{SourceId, SourceTagged, Termination};
+
+    #[test]
+    fn vec_altern() {
+        let vec1 = vec![1, 4, 7, 9];
+        let vec2 = vec![2, 5];
+        let vec3 = vec![3, 6, 8];
+
+        let iter = VecAltern::new().add_and(vec1.iter()).add_and(vec2.iter()).add_and(vec3.iter());
+
+        assert_eq!(iter.collect::<Vec<_>>(), vec![&1,&2,&3,&4,&5,&6,&7,&8, &9]);
+    }
+
+    #[test]
+    fn empty_vec_altern() {
+        let vec1 : Vec<u8> = vec![];
+        let vec2: Vec<u8> = vec![];
+        let vec3: Vec<u8> = vec![];
+
+        let mut iter = VecAltern::new().add_and(vec1.iter()).add_and(vec2.iter()).add_and(vec3.iter());
+
+        assert_eq!(iter.next(), None);
+    }
+
+    use crate::altern;
+    #[test]
+    fn macro_altern() {
+        let vec1 = vec![1, 4, 7, 9];
+        let vec2 = vec![2, 5];
+        let vec3 = vec![3, 6, 8];
+        let iter = altern!(vec1.iter(), vec2.iter(), vec3.iter());
+        assert_eq!(iter.collect::<Vec<_>>(), vec![&1,&2,&3,&4,&5,&6,&7,&8, &9]);
+    }
+
+    #[test]
+    fn reverse() {
+        let vec1 = [1, 4, 7, 9];
+        let vec2 = [2, 5];
+        let vec3 = [3, 6, 8];
+
+        let iter = VecAltern::from(vec![vec1.iter(), vec2.iter(), vec3.iter()]).rev();
+
+        assert_eq!(iter.collect::<Vec<_>>(), vec![&9,&8,&7,&6,&5,&4,&3,&2,&1]);
+    }
+
+    #[test]
+    fn next_and_next_back() {
+        let vec1 = [1, 4, 7, 9];
+        let vec2 = [2, 5];
+        let vec3 = [3, 6, 8];
+
+        let mut iter = VecAltern::from(vec![vec1.iter(), vec2.iter(), vec3.iter()]);
+
+        assert_eq!(iter.next(), Some(&1));
+        assert_eq!(iter.next(), Some(&2));
+        assert_eq!(iter.next_back(), Some(&9));
+        assert_eq!(iter.next_back(), Some(&8));
+        assert_eq!(iter.next(), Some(&3));
+        assert_eq!(iter.next_back(), Some(&7));
+        assert_eq!(iter.next(), Some(&4));
+        assert_eq!(iter.next_back(), Some(&6));
+        assert_eq!(iter.next(), Some(&5));
+        assert_eq!(iter.next_back(), None);
+        assert_eq!(iter.next(), None);
+    }
+
+    #[test]
+    fn reverse_mirrors_forward() {
+        let lens = [3, 0, 5, 1, 5, 2];
+        let iters = || VecAltern::from(lens.iter().enumerate().map(|(i, &len)| (0..len).map(move |j| (i, j))).collect::<Vec<_>>());
+
+        let mut forward = iters().collect::<Vec<_>>();
+        forward.reverse();
+        assert_eq!(iters().rev().collect::<Vec<_>>(), forward);
+    }
+
+    #[test]
+    fn add_during_iteration() {
+        let vec1 = [1, 4, 7];
+        let vec2 = [2];
+        let vec3 = [3, 5, 8];
+        let vec4 = [6, 9];
+
+        let mut iter = VecAltern::new().add_and(vec1.iter()).add_and(vec2.iter()).add_and(vec3.iter());
+        assert_eq!(iter.next(), Some(&1));
+        assert_eq!(iter.next(), Some(&2));
+        assert_eq!(iter.next(), Some(&3));
+        assert_eq!(iter.next(), Some(&4));
+        iter.add(vec4.iter());
+        assert_eq!(iter.collect::<Vec<_>>(), vec![&5, &6, &7, &8, &9]);
+    }
+
+    #[test]
+    fn add_after_exhaustion() {
+        let vec1 = [1];
+        let vec2 = [2, 3];
+
+        let mut iter = VecAltern::new().add_and(vec1.iter());
+        assert_eq!(iter.next(), Some(&1));
+        assert_eq!(iter.next(), None);
+        iter.add(vec2.iter());
+        assert_eq!(iter.collect::<Vec<_>>(), vec![&2, &3]);
+    }
+
+    #[test]
+    fn many_skewed_iterators() {
+        let lens = (0..1000).map(|i| if i % 7 == 0 { 20 } else { i % 3 }).collect::<Vec<usize>>();
+
+        let mut expected = vec![];
+        for round in 0..20 {
+            for (i, len) in lens.iter().enumerate() {
+                if round < *len {
+                    expected.push((i, round));
+                }
+            }
+        }
+
+        let iter = VecAltern::from(lens.iter().enumerate().map(|(i, &len)| (0..len).map(move |j| (i, j))).collect::<Vec<_>>());
+        assert_eq!(iter.collect::<Vec<_>>(), expected);
+    }
+
+    #[test]
+    fn weighted() {
+        let mut iter = VecAltern::new();
+        iter.add_weighted(std::iter::repeat_n('a', 9), 3);
+        iter.add(std::iter::repeat_n('b', 3));
+        assert_eq!(iter.collect::<String>(), "aabaaabaaaba");
+
+        let mut iter = VecAltern::new();
+        iter.add_weighted(std::iter::repeat_n('a', 10), 5);
+        iter.add(std::iter::repeat_n('b', 2));
+        iter.add(std::iter::repeat_n('c', 2));
+        assert_eq!(iter.collect::<String>(), "aaabcaaaaabcaa");
+    }
+
+    #[test]
+    fn weighted_exhaustion() {
+        let mut iter = VecAltern::new();
+        iter.add_weighted(std::iter::repeat_n('a', 2), 2);
+        iter.add_weighted(std::iter::repeat_n('b', 6), 2);
+        iter.add(std::iter::repeat_n('c', 5));
+        assert_eq!(iter.collect::<String>(), "abcabbcbbcbcc");
+    }
+
+    #[test]
+    fn coprime_weights() {
+        let mut iter = VecAltern::default().with_termination(Termination::Shortest);
+        iter.push_weighted(0..3_000_000, 1_000_003);
+        iter.push_weighted(10..12, 999_983);
+        assert_eq!(iter.by_ref().take(4).collect::<Vec<_>>(), vec![0, 10, 1, 11]);
+        assert_eq!(iter.size_hint(), (1, Some(1)));
+        assert_eq!(iter.collect::<Vec<_>>(), vec![2]);
+    }
+
+    #[test]
+    fn weighted_add_during_iteration() {
+        let mut iter = VecAltern::new().add_and(std::iter::repeat_n('a', 4)).add_and(std::iter::repeat_n('b', 4));
+        assert_eq!(iter.next(), Some('a'));
+        iter.add_weighted(std::iter::repeat_n('c', 4), 2);
+        assert_eq!(iter.collect::<String>(), "cbaccbacbab");
+    }
+
+    #[test]
+    fn weighted_reverse_mirrors_forward() {
+        let lens = [3, 0, 9, 1, 5, 2, 4];
+        let weights = [1, 4, 3, 1, 2, 2, 1];
+        let iters = || {
+            let mut iter = VecAltern::default();
+            for (i, (&len, &weight)) in lens.iter().zip(weights.iter()).enumerate() {
+                iter.push_weighted((0..len).map(move |j| (i, j)), weight);
+            }
+            iter
+        };
+
+        let mut forward = iters().collect::<Vec<_>>();
+        forward.reverse();
+        assert_eq!(iters().rev().collect::<Vec<_>>(), forward);
+
+        let mut iter = iters();
+        let mut front = vec![];
+        let mut back = vec![];
+        while let Some(value) = iter.next() {
+            front.push(value);
+            if let Some(value) = iter.next_back() {
+                back.push(value);
+            }
+        }
+        back.reverse();
+        front.extend(back);
+        forward.reverse();
+        assert_eq!(front, forward);
+    }
+
+    #[test]
+    fn shortest() {
+        let vec1 = [1, 4, 7];
+        let vec2 = [2, 5];
+        let vec3 = [3, 6, 8];
+
+        let mut iter = VecAltern::new().add_and(vec1.iter()).add_and(vec2.iter()).add_and(vec3.iter())
+            .with_termination(Termination::Shortest);
+        assert_eq!(iter.by_ref().collect::<Vec<_>>(), vec![&1, &2, &3, &4, &5, &6, &7]);
+        assert_eq!(iter.next(), None);
+
+        let iter = VecAltern::from(vec![1..3, 3..9, 9..12]).with_termination(Termination::Shortest);
+        assert_eq!(iter.len(), 6);
+        assert_eq!(iter.collect::<Vec<_>>(), vec![1, 3, 9, 2, 4, 10]);
+    }
+
+    #[test]
+    fn shortest_weighted() {
+        let mut iter = VecAltern::default().with_termination(Termination::Shortest);
+        iter.push_weighted(std::iter::repeat_n('a', 4), 3);
+        iter.push(std::iter::repeat_n('b', 5));
+        assert_eq!(iter.collect::<String>(), "aabaa");
+    }
+
+    #[test]
+    fn complete_rounds() {
+        let iter = VecAltern::from(vec![1..3, 3..9, 9..12]).with_complete_rounds();
+        assert_eq!(iter.len(), 6);
+        assert_eq!(iter.collect::<Vec<_>>(), vec![1, 3, 9, 2, 4, 10]);
+
+        let iter = VecAltern::from(vec![1..4, 4..6, 6..9]).with_complete_rounds();
+        assert_eq!(iter.collect::<Vec<_>>(), vec![1, 4, 6, 2, 5, 7]);
+
+        let iter = VecAltern::<usize, std::ops::Range<usize>>::default().with_complete_rounds();
+        assert_eq!(iter.len(), 0);
+    }
+
+    #[test]
+    fn complete_rounds_from_size_hints() {
+        let iter = VecAltern::new().add_and(1..4).add_and(vec![4, 5].into_iter()).add_and(6..9).with_complete_rounds();
+        assert_eq!(iter.collect::<Vec<_>>(), vec![1, 4, 6, 2, 5, 7]);
+
+        // The size hint of a filtered iterator is not exact, so each round is taken before it is yielded.
+        let mut iter = VecAltern::new().add_and((1..4).filter(|_| true)).add_and(4..6).with_complete_rounds();
+        assert_eq!(iter.size_hint(), (0, Some(4)));
+        assert_eq!(iter.next(), Some(1));
+        assert_eq!(iter.size_hint(), (1, Some(3)));
+        assert_eq!(iter.collect::<Vec<_>>(), vec![4, 2, 5]);
+
+        let iter = VecAltern::new().add_and((1..4).filter(|value| value % 2 == 1)).add_and(4..6).with_complete_rounds();
+        assert_eq!(iter.collect::<Vec<_>>(), vec![1, 4, 3, 5]);
+    }
+
+    #[test]
+    fn complete_rounds_then_add() {
+        let mut iter = VecAltern::default();
+        iter.push(0..3);
+        let mut iter = iter.with_complete_rounds();
+        iter.push(10..12);
+        assert_eq!(iter.len(), 4);
+        assert_eq!(iter.collect::<Vec<_>>(), vec![0, 10, 1, 11]);
+
+        // The round in progress ends before the added iterator is visited.
+        let mut iter = VecAltern::from(vec![0..4, 10..14]).with_complete_rounds();
+        assert_eq!(iter.next(), Some(0));
+        iter.push(20..21);
+        assert_eq!(iter.len(), 4);
+        assert_eq!(iter.collect::<Vec<_>>(), vec![10, 1, 11, 20]);
+
+        let mut iter = VecAltern::default();
+        iter.push(0..3);
+        let mut iter = iter.with_complete_rounds();
+        iter.push(10..12);
+        assert_eq!(iter.rev().collect::<Vec<_>>(), vec![11, 1, 10, 0]);
+    }
+
+    #[test]
+    fn complete_rounds_then_handle_push() {
+        let mut iter = VecAltern::default();
+        iter.push(0..3);
+        let mut iter = iter.with_complete_rounds();
+        iter.handle().push(10..12);
+        assert_eq!(iter.len(), 4);
+        assert_eq!(iter.collect::<Vec<_>>(), vec![0, 10, 1, 11]);
+
+        let mut iter = VecAltern::from(vec![0..4, 10..14]).with_complete_rounds();
+        let handle = iter.handle();
+        assert_eq!(iter.next(), Some(0));
+        handle.push(20..21);
+        assert_eq!(iter.len(), 4);
+        assert_eq!(iter.collect::<Vec<_>>(), vec![10, 1, 11, 20]);
+
+        let mut iter = VecAltern::from(vec![0..3, 10..11]).with_complete_rounds();
+        iter.handle().remove(SourceId(1));
+        assert_eq!(iter.len(), 3);
+        assert_eq!(iter.collect::<Vec<_>>(), vec![0, 1, 2]);
+    }
+
+    #[test]
+    fn complete_rounds_buffered_matches_exact() {
+        let lens = [3, 7, 5, 4, 5, 6];
+        for weights in [[1, 1, 1, 1, 1, 1], [1, 4, 3, 1, 2, 2]] {
+            for chunk in 1..4 {
+                for skip in 0..lens.len() {
+                    let mut exact = VecAltern::default().with_chunk(chunk);
+                    let mut filtered = VecAltern::new().with_chunk(chunk);
+                    for (i, (&len, &weight)) in lens.iter().zip(weights.iter()).enumerate().skip(skip) {
+                        exact.push_weighted((0..len).map(move |j| (i, j)), weight);
+                        filtered.add_weighted((0..len).map(move |j| (i, j)).filter(|_| true), weight);
+                    }
+                    let (exact, mut filtered) = (exact.with_complete_rounds(), filtered.with_complete_rounds());
+                    let expected = exact.enumerate_sources().collect::<Vec<_>>();
+                    assert_eq!(filtered.size_hint().1, Some(expected.len()));
+                    let mut tagged = vec![];
+                    while let Some(next) = filtered.next_tagged() {
+                        tagged.push(next);
+                        let (lower, upper) = filtered.size_hint();
+                        assert!(lower <= expected.len() - tagged.len() && upper >= Some(expected.len() - tagged.len()));
+                    }
+                    assert_eq!(tagged, expected);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn shortest_reverse_mirrors_forward() {
+        let lens = [3, 7, 5, 4, 5, 6];
+        for weights in [[1, 1, 1, 1, 1, 1], [1, 4, 3, 1, 2, 2]] {
+            for skip in 0..lens.len() {
+                let iters = |rounds: bool| {
+                    let mut iter = VecAltern::default();
+                    for (i, (&len, &weight)) in lens.iter().zip(weights.iter()).enumerate().skip(skip) {
+                        iter.push_weighted((0..len).map(move |j| (i, j)), weight);
+                    }
+                    if rounds { iter.with_complete_rounds() } else { iter.with_termination(Termination::Shortest) }
+                };
+
+                for rounds in [false, true] {
+                    let mut forward = iters(rounds).collect::<Vec<_>>();
+                    assert_eq!(iters(rounds).len(), forward.len());
+                    forward.reverse();
+                    assert_eq!(iters(rounds).rev().collect::<Vec<_>>(), forward);
+
+                    let mut iter = iters(rounds);
+                    let first = iter.next();
+                    assert_eq!(iter.rev().chain(first).collect::<Vec<_>>(), forward);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn chunked() {
+        let iter = VecAltern::from(vec![0..5, 10..12, 20..24]).with_chunk(2);
+        assert_eq!(iter.collect::<Vec<_>>(), vec![0, 1, 10, 11, 20, 21, 2, 3, 22, 23, 4]);
+
+        let iter = VecAltern::from(vec![0..5, 10..12, 20..24]).with_chunk(2).with_termination(Termination::Shortest);
+        assert_eq!(iter.collect::<Vec<_>>(), vec![0, 1, 10, 11, 20, 21, 2, 3]);
+
+        let iter = VecAltern::from(vec![0..5, 10..13, 20..24]).with_chunk(2).with_complete_rounds();
+        assert_eq!(iter.collect::<Vec<_>>(), vec![0, 1, 10, 11, 20, 21]);
+
+        let mut iter = VecAltern::default().with_chunk(2);
+        iter.push_weighted(0..6, 2);
+        iter.push(10..14);
+        assert_eq!(iter.collect::<Vec<_>>(), vec![0, 1, 10, 11, 2, 3, 4, 5, 12, 13]);
+    }
+
+    #[test]
+    fn chunked_changes_during_iteration() {
+        let mut iter = VecAltern::from(vec![0..6, 10..16]).with_chunk(3);
+        assert_eq!(iter.next(), Some(0));
+        assert_eq!(iter.next(), Some(1));
+        let first = SourceId(0);
+        iter.pause(first);
+        assert_eq!(iter.by_ref().take(3).collect::<Vec<_>>(), vec![10, 11, 12]);
+        iter.resume(first);
+        assert_eq!(iter.collect::<Vec<_>>(), vec![13, 14, 15, 2, 3, 4, 5]);
+
+        let mut iter = VecAltern::default().with_chunk(2);
+        iter.push(0..4);
+        assert_eq!(iter.next(), Some(0));
+        iter.push(10..14);
+        assert_eq!(iter.collect::<Vec<_>>(), vec![1, 10, 11, 2, 3, 12, 13]);
+    }
+
+    #[test]
+    fn chunked_matches_forward() {
+        let lens = [3, 7, 0, 5, 4, 6];
+        for weights in [[1, 1, 1, 1, 1, 1], [1, 4, 3, 1, 2, 2]] {
+            for chunk in 1..4 {
+                for skip in 0..lens.len() {
+                    for termination in [None, Some(false), Some(true)] {
+                        let iters = || {
+                            let mut iter = VecAltern::default().with_chunk(chunk);
+                            for (i, (&len, &weight)) in lens.iter().zip(weights.iter()).enumerate().skip(skip) {
+                                iter.push_weighted((0..len).map(move |j| (i, j)), weight);
+                            }
+                            match termination {
+                                None => iter,
+                                Some(false) => iter.with_termination(Termination::Shortest),
+                                Some(true) => iter.with_complete_rounds(),
+                            }
+                        };
+
+                        let expected = iters().collect::<Vec<_>>();
+                        assert_eq!(iters().len(), expected.len());
+                        assert_eq!(iters().count(), expected.len());
+                        assert_eq!(iters().rev().collect::<Vec<_>>(), expected.iter().rev().copied().collect::<Vec<_>>());
+                        for n in 0..expected.len() + 2 {
+                            let mut nth = iters();
+                            assert_eq!(nth.nth(n), expected.get(n).copied());
+                            assert_eq!(nth.rev().collect::<Vec<_>>(), expected.get(n + 1..).unwrap_or_default().iter().rev().copied().collect::<Vec<_>>());
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn zero_chunk() {
+        VecAltern::from(vec![0..3, 0..3]).with_chunk(0);
+    }
+
+    #[test]
+    fn pause_and_resume() {
+        let mut iter = VecAltern::from(vec![0..4, 10..14, 20..24]);
+        assert_eq!(iter.next(), Some(0));
+        assert!(iter.pause(SourceId(1)));
+        assert!(!iter.pause(SourceId(1)));
+        assert_eq!(iter.len(), 7);
+        assert_eq!(iter.next(), Some(20));
+        assert_eq!(iter.next(), Some(1));
+        assert!(iter.resume(SourceId(1)));
+        assert!(!iter.resume(SourceId(1)));
+        assert_eq!(iter.collect::<Vec<_>>(), vec![21, 10, 2, 22, 11, 3, 23, 12, 13]);
+    }
+
+    #[test]
+    fn pause_weighted() {
+        let mut iter = VecAltern::default();
+        iter.push_weighted(std::iter::repeat_n('a', 6), 2);
+        let b = iter.push(std::iter::repeat_n('b', 3));
+        assert_eq!(iter.next(), Some('a'));
+        iter.pause(b);
+        assert_eq!(iter.by_ref().take(3).collect::<String>(), "aaa");
+        iter.resume(b);
+        assert_eq!(iter.collect::<String>(), "ababb");
+    }
+
+    #[test]
+    fn remove() {
+        let mut iter = VecAltern::from(vec![0..4, 10..14, 20..24]);
+        assert_eq!(iter.next(), Some(0));
+        assert!(iter.remove(SourceId(1)));
+        assert!(iter.pause(SourceId(2)));
+        assert!(iter.remove(SourceId(2)));
+        assert!(!iter.remove(SourceId(2)));
+        assert!(!iter.remove(SourceId(7)));
+        assert_eq!(iter.collect::<Vec<_>>(), vec![1, 2, 3]);
+    }
+
+    #[test]
+    fn source_id_is_tag() {
+        let mut iter = VecAltern::new();
+        let first = iter.add(0..2);
+        let second = iter.add(10..12);
+        assert_eq!(iter.enumerate_sources().map(|(source, _)| source).collect::<Vec<_>>(),
+            vec![first.index(), second.index(), first.index(), second.index()]);
+    }
+
+    #[test]
+    fn handle() {
+        let mut iter = VecAltern::new();
+        let first = iter.add(0..3);
+        iter.add(10..13);
+        let handle = iter.handle();
+        let mut added = None;
+        let mut results = vec![];
+
+        let mut seen = vec![];
+        for value in iter.by_ref() {
+            match value {
+                0 => results.push(handle.pause(first)),
+                11 => results.push(handle.resume(first)),
+                12 => added = Some(handle.add(20..22)),
+                _ => {},
+            }
+            seen.push(value);
+        }
+        assert_eq!(seen, vec![0, 10, 11, 12, 1, 20, 2, 21]);
+        assert_eq!(results.iter().map(|result| result.get()).collect::<Vec<_>>(), vec![Some(true), Some(true)]);
+        assert_eq!(added, Some(SourceId(2)));
+        assert_eq!(iter.push(Box::new(30..31)), SourceId(3));
+        assert_eq!(handle.add(40..41), SourceId(4));
+        assert_eq!(iter.enumerate_sources().collect::<Vec<_>>(), vec![(3, 30), (4, 40)]);
+    }
+
+    #[test]
+    fn handle_size_hint() {
+        let mut iter = VecAltern::from(vec![0..3, 10..13]);
+        let handle = iter.handle();
+        let added = handle.push(100..105);
+        assert_eq!(iter.len(), 11);
+        assert_eq!(iter.size_hint(), (11, Some(11)));
+        handle.pause(SourceId(0));
+        handle.remove(added);
+        assert_eq!(iter.len(), 3);
+        handle.resume(SourceId(0));
+        assert_eq!(iter.next(), Some(10));
+        assert_eq!(iter.len(), 5);
+        assert_eq!(iter.clone().count(), 5);
+
+        let mut iter = VecAltern::from(vec![0..3, 10..13]).with_termination(Termination::Shortest);
+        let handle = iter.handle();
+        handle.push_weighted(100..102, 2);
+        assert_eq!(iter.len(), 4);
+        assert_eq!(iter.collect::<Vec<_>>(), vec![100, 0, 10, 101]);
+    }
+
+    #[test]
+    fn handle_results() {
+        let mut iter = VecAltern::from(vec![0..3, 10..13]);
+        let handle = iter.handle();
+        let removed = handle.remove(SourceId(1));
+        let resumed = handle.resume(SourceId(0));
+        let unknown = handle.pause(SourceId(5));
+        // Neither the size hint nor a clone report the results.
+        assert_eq!(iter.len(), 3);
+        assert_eq!(iter.clone().count(), 3);
+        assert_eq!((removed.get(), resumed.get(), unknown.get()), (None, None, None));
+
+        assert_eq!(iter.next(), Some(0));
+        assert_eq!((removed.get(), resumed.get(), unknown.get()), (Some(true), Some(false), Some(false)));
+        let dropped = handle.remove(SourceId(1));
+        drop(iter);
+        assert_eq!(dropped.get(), None);
+    }
+
+    #[test]
+    fn handle_from_thread() {
+        let mut iter = VecAltern::from(vec![0..3, 10..13]);
+        let handle = iter.handle();
+        std::thread::spawn(move || {
+            handle.push(20..23);
+            handle.remove(SourceId(0));
+        }).join().unwrap();
+        assert_eq!(iter.collect::<Vec<_>>(), vec![10, 20, 11, 21, 12, 22]);
+    }
+
+    #[test]
+    fn internal_iteration() {
+        let lens = [3, 0, 9, 1, 5, 2, 4];
+        for weights in [[1, 1, 1, 1, 1, 1, 1], [1, 4, 3, 1, 2, 2, 1]] {
+            for skip in 0..lens.len() {
+                for filter in [false, true] {
+                    // Filtered iterators have a lower bound of 0, so nothing can be skipped.
+                    let iters = || {
+                        let mut iter = VecAltern::new();
+                        for (i, (&len, &weight)) in lens.iter().zip(weights.iter()).enumerate().skip(skip) {
+                            iter.add_weighted((0..len).map(move |j| (i, j)).filter(move |_| !filter || len > 0), weight);
+                        }
+                        iter
+                    };
+                    let expected = iters().collect::<Vec<_>>();
+                    assert_eq!(iters().fold(vec![], |mut acc, value| { acc.push(value); acc }), expected);
+                    assert_eq!(iters().count(), expected.len());
+                    for n in 0..expected.len() + 2 {
+                        let mut nth = iters();
+                        assert_eq!(nth.nth(n), expected.get(n).copied());
+                        assert_eq!(nth.collect::<Vec<_>>(), expected.get(n + 1..).unwrap_or_default());
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn internal_iteration_with_handle() {
+        let mut iter = VecAltern::from(vec![0..3, 10..13]);
+        let handle = iter.handle();
+        let sum = iter.fold(0, |sum, value| {
+            if value == 10 {
+                handle.push(20..22);
+            }
+            sum + value
+        });
+        assert_eq!(sum, 3 + 33 + 41);
+
+        let iter = VecAltern::from(vec![1..3, 3..9, 9..12]).with_termination(Termination::Shortest);
+        assert_eq!(iter.count(), 6);
+    }
+
+    #[test]
+    fn nth_skips_in_bulk() {
+        let mut iter = VecAltern::from(vec![0..usize::MAX, 0..usize::MAX, 0..usize::MAX]);
+        assert_eq!(iter.nth(usize::MAX - 1), Some(usize::MAX / 3 - 1));
+        assert_eq!(iter.next(), Some(usize::MAX / 3));
+
+        let iter = VecAltern::from(vec![0..usize::MAX / 4, 0..usize::MAX / 4, 0..10]);
+        assert_eq!(iter.count(), usize::MAX / 4 * 2 + 10);
+    }
+
+    #[test]
+    #[should_panic]
+    fn zero_weight() {
+        VecAltern::new().add_weighted(0..3, 0);
+    }
+
+    #[test]
+    fn size_hint() {
+        let vec1 = [1, 4, 7, 9];
+        let iter = altern!(vec1.iter(), [0, 1, 2].iter(), vec1.iter().filter(|x| **x > 4));
+        assert_eq!(iter.size_hint(), (7, Some(11)));
+
+        let iter = altern!(vec1.iter(), std::iter::repeat(&0));
+        assert_eq!(iter.size_hint(), (usize::MAX, None));
+
+        let iter = altern!(0..usize::MAX, 0..usize::MAX);
+        assert_eq!(iter.size_hint(), (usize::MAX, None));
+    }
+
+    #[test]
+    fn size_hint_after_exhaustion() {
+        let vec1 = [1, 4, 7, 9];
+        let vec2 = [2];
+        let mut iter = altern!(vec1.iter(), vec2.iter());
+        iter.next();
+        iter.next();
+        iter.next();
+        assert_eq!(iter.size_hint(), (2, Some(2)));
+    }
+
+    #[test]
+    fn len() {
+        let vec1 = [1, 4, 7, 9];
+        let vec2 = [2, 5];
+        let vec3 = [3, 6, 8];
+
+        let mut iter = VecAltern::from(vec![vec1.iter(), vec2.iter(), vec3.iter()]);
+        assert_eq!(iter.len(), 9);
+        iter.next();
+        iter.next_back();
+        assert_eq!(iter.len(), 7);
+    }
+
+    #[test]
+    fn peek_skips_exhausted() {
+        let mut iter = VecAltern::from(vec![0..1, 10..13, 20..21]);
+        assert_eq!(iter.by_ref().take(3).collect::<Vec<_>>(), vec![0, 10, 20]);
+        assert_eq!(iter.peek_source(), Some(1));
+        assert_eq!(iter.peek(), Some(&11));
+        assert_eq!(iter.len(), 2);
+        assert_eq!(iter.next_if(|value| *value > 11), None);
+        assert_eq!(iter.next_tagged(), Some((1, 11)));
+        assert_eq!(iter.next_if(|value| *value > 11), Some(12));
+        assert_eq!(iter.peek(), None);
+        assert_eq!(iter.peek_source(), None);
+        assert_eq!(iter.size_hint(), (0, Some(0)));
+    }
+
+    #[test]
+    fn peek_then_add_or_resume() {
+        let mut iter = VecAltern::new();
+        assert_eq!(iter.peek(), None);
+        iter.add(0..2);
+        assert_eq!(iter.peek(), Some(&0));
+        // Like after `next`, the added iterator comes at the end of the round.
+        iter.add(10..12);
+        assert_eq!(iter.collect::<Vec<_>>(), vec![0, 1, 10, 11]);
+
+        let mut iter = VecAltern::from(vec![0..2, 10..12]);
+        let first = SourceId(0);
+        assert_eq!(iter.peek(), Some(&0));
+        iter.pause(first);
+        assert_eq!(iter.next(), Some(0));
+        assert_eq!(iter.by_ref().take(2).collect::<Vec<_>>(), vec![10, 11]);
+        assert_eq!(iter.peek(), None);
+        iter.resume(first);
+        assert_eq!(iter.collect::<Vec<_>>(), vec![1]);
+    }
+
+    #[test]
+    fn peek_then_internal_iteration() {
+        let iter = || {
+            let mut iter = VecAltern::from(vec![0..5, 10..12, 20..21]);
+            assert_eq!(iter.peek(), Some(&0));
+            iter
+        };
+        assert_eq!(iter().collect::<Vec<_>>(), vec![0, 10, 20, 1, 11, 2, 3, 4]);
+        assert_eq!(iter().count(), 8);
+        assert_eq!(iter().nth(1), Some(10));
+        assert_eq!(iter().nth(3), Some(1));
+        assert_eq!(iter().rev().collect::<Vec<_>>(), vec![4, 3, 2, 11, 1, 20, 10, 0]);
+
+        let mut iter = VecAltern::from(vec![0..3, 10..11]).with_termination(Termination::Shortest);
+        assert_eq!(iter.peek(), Some(&0));
+        assert_eq!(iter.next_back(), Some(1));
+        assert_eq!(iter.collect::<Vec<_>>(), vec![0, 10]);
+    }
+
+    #[test]
+    fn clone_replays() {
+        let mut iter = VecAltern::from(vec![0..3, 10..12, 20..21]).with_chunk(2);
+        assert_eq!(iter.next(), Some(0));
+        let copy = iter.clone();
+        assert_eq!(iter.collect::<Vec<_>>(), vec![1, 10, 11, 20, 2]);
+        assert_eq!(copy.collect::<Vec<_>>(), vec![1, 10, 11, 20, 2]);
+    }
+
+    #[test]
+    fn clone_applies_pending_commands() {
+        use super::CloneVecAltern;
+        let mut iter = CloneVecAltern::default().add_cloneable_and(0..2);
+        let handle = iter.handle();
+        handle.push(Box::new(vec![10, 11].into_iter()));
+        let copy = iter.clone();
+        handle.push(Box::new(20..21));
+        assert_eq!(copy.collect::<Vec<_>>(), vec![0, 10, 1, 11]);
+        assert_eq!(iter.collect::<Vec<_>>(), vec![0, 10, 20, 1, 11]);
+    }
+
+    #[test]
+    fn debug() {
+        let mut iter = VecAltern::from(vec![0..1, 10..12, 20..22]);
+        iter.pause(SourceId(2));
+        assert_eq!(iter.next(), Some(0));
+        assert_eq!(
+            format!("{iter:?}"),
+            "VecAltern { sources: [1, 0], paused: [2], next: Some(1), chunk: 1, termination: Longest, limit: None, peeked_source: None }"
+        );
+        assert_eq!(iter.peek(), Some(&10));
+        assert_eq!(
+            format!("{iter:?}"),
+            "VecAltern { sources: [0, 1], paused: [2], next: Some(0), chunk: 1, termination: Longest, limit: None, peeked_source: Some(1) }"
+        );
+    }
+
+    #[test]
+    fn next_after_end() {
+        let mut iter = VecAltern::new().add_and(std::iter::once(0)).add_and(std::iter::from_fn(|| None));
+        assert_eq!(iter.by_ref().count(), 1);
+        assert_eq!(iter.next(), None);
+        assert_eq!(iter.next(), None);
+        let id = iter.add(1..3);
+        assert_eq!(iter.next(), Some(1));
+        iter.pause(id);
+        assert_eq!(iter.next(), None);
+        iter.resume(id);
+        assert_eq!(iter.next(), Some(2));
+    }
+
+    #[test]
+    fn fused() {
+        use std::iter::FusedIterator;
+        fn assert_fused<T: FusedIterator>(_: &T) {}
+
+        let mut iter = VecAltern::new().add_and(std::iter::once(0)).into_fused().unwrap();
+        assert_fused(&iter);
+        assert_eq!(iter.next(), Some(0));
+        assert_eq!(iter.next(), None);
+        let mut iter = iter.into_inner();
+        iter.add(1..2);
+        assert_eq!(iter.next(), Some(1));
+
+        let mut iter = VecAltern::from(vec![0..2, 10..12]);
+        let handle = iter.handle();
+        handle.pause(SourceId(0));
+        let mut iter = iter.into_fused().unwrap_err();
+        assert_eq!(iter.next(), Some(10));
+        handle.resume(SourceId(0));
+        drop(handle);
+        let iter = iter.into_fused().unwrap();
+        assert_eq!(iter.len(), 3);
+        assert_eq!(iter.collect::<Vec<_>>(), vec![11, 0, 1]);
+    }
+
+    #[test]
+    fn send_and_sync() {
+        use super::{SendVecAltern, SyncVecAltern};
+        fn assert_send<T: Send>(_: &T) {}
+        fn assert_sync<T: Send + Sync>(_: &T) {}
+
+        let mut iter = SendVecAltern::default().add_send_and(0..3);
+        let handle = iter.handle();
+        std::thread::spawn(move || handle.push(Box::new(vec![10, 11].into_iter()))).join().unwrap();
+        assert_send(&iter);
+        let worker = std::thread::spawn(move || iter.collect::<Vec<_>>());
+        assert_eq!(worker.join().unwrap(), vec![0, 10, 1, 11, 2]);
+
+        let mut iter = SyncVecAltern::default().add_sync_and(0..2);
+        iter.add_sync(10..12);
+        assert_sync(&iter);
+        assert_eq!(std::thread::scope(|scope| scope.spawn(|| iter.size_hint()).join().unwrap()), (4, Some(4)));
+        assert_eq!(iter.collect::<Vec<_>>(), vec![0, 10, 1, 11]);
+    }
+}
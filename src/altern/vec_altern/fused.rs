@@ -0,0 +1,140 @@
+//! A `VecAltern` whose iterators can no longer change, so that it is fused.
+
+use std::fmt;
+use std::iter::FusedIterator;
+use std::sync::Arc;
+
+use super::VecAltern;
+use crate::altern::SourceTagged;
+
+/// A `VecAltern` whose iterators can no longer be added, paused, resumed or removed, created by
+/// `VecAltern::into_fused`.
+///
+/// Once `next` returned `None`, nothing can restart the alternation, so it implements `FusedIterator`.
+#[derive(Clone)]
+pub struct FusedVecAltern<'a, A, I = Box<dyn Iterator<Item = A> + 'a>>(VecAltern<'a, A, I>)
+where
+    I: Iterator<Item = A>;
+
+impl<'a, A, I> VecAltern<'a, A, I>
+where
+    I: Iterator<Item = A>
+{
+    /// Freezes the iterators of the alternation, so that it implements `FusedIterator`.
+    ///
+    /// The changes requested by the handles dropped since are applied first.
+    ///
+    /// # Errors
+    ///
+    /// Gives back the `VecAltern` if one of its handles is still alive, as it could change the iterators.
+    ///
+    /// # Example
+    /// ```
+    /// use combin_iterator::altern::VecAltern;
+    /// let mut iter = VecAltern::from(vec![0..2, 10..11]);
+    /// let handle = iter.handle();
+    /// handle.push(20..21);
+    ///
+    /// let iter = iter.into_fused().unwrap_err();
+    /// drop(handle);
+    /// let mut iter = iter.into_fused().unwrap();
+    /// assert_eq!(iter.by_ref().collect::<Vec<_>>(), vec![0, 10, 20, 1]);
+    /// assert_eq!(iter.next(), None);
+    /// ```
+    // Like `Arc::try_unwrap`, it gives back the `VecAltern` itself, large as it is.
+    #[allow(clippy::result_large_err)]
+    pub fn into_fused(mut self) -> Result<FusedVecAltern<'a, A, I>, Self> {
+        if let Some(shared) = self.shared.as_ref() {
+            if Arc::strong_count(shared) > 1 {
+                return Err(self);
+            }
+        }
+        self.sync();
+        self.shared = None;
+        Ok(FusedVecAltern(self))
+    }
+}
+
+impl<'a, A, I> FusedVecAltern<'a, A, I>
+where
+    I: Iterator<Item = A>
+{
+    /// Returns the `VecAltern`, to change its iterators again.
+    pub fn into_inner(self) -> VecAltern<'a, A, I> {
+        self.0
+    }
+
+    /// Returns a reference to the next element, without consuming it, like `VecAltern::peek`.
+    pub fn peek(&mut self) -> Option<&A> {
+        self.0.peek()
+    }
+}
+
+impl<'a, A, I> fmt::Debug for FusedVecAltern<'a, A, I>
+where
+    I: Iterator<Item = A>
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("FusedVecAltern").field(&self.0).finish()
+    }
+}
+
+impl<'a, A, I> Iterator for FusedVecAltern<'a, A, I>
+where
+    I: Iterator<Item = A>
+{
+    type Item = A;
+
+    fn next(&mut self) -> Option<A> {
+        self.0.next()
+    }
+
+    fn fold<B, F>(self, init: B, f: F) -> B
+    where
+        F: FnMut(B, A) -> B
+    {
+        self.0.fold(init, f)
+    }
+
+    fn nth(&mut self, n: usize) -> Option<A> {
+        self.0.nth(n)
+    }
+
+    fn count(self) -> usize {
+        self.0.count()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.0.size_hint()
+    }
+}
+
+impl<'a, A, I> SourceTagged for FusedVecAltern<'a, A, I>
+where
+    I: Iterator<Item = A>
+{
+    fn source_count(&self) -> usize {
+        self.0.source_count()
+    }
+
+    fn next_tagged(&mut self) -> Option<(usize, A)> {
+        self.0.next_tagged()
+    }
+}
+
+impl<'a, A, I> DoubleEndedIterator for FusedVecAltern<'a, A, I>
+where
+    I: DoubleEndedIterator<Item = A> + ExactSizeIterator,
+{
+    fn next_back(&mut self) -> Option<A> {
+        self.0.next_back()
+    }
+}
+
+impl<'a, A, I> ExactSizeIterator for FusedVecAltern<'a, A, I>
+where
+    I: ExactSizeIterator<Item = A> {}
+
+impl<'a, A, I> FusedIterator for FusedVecAltern<'a, A, I>
+where
+    I: Iterator<Item = A> {}
@@ -0,0 +1,182 @@
+//! The control handle of a `VecAltern`, to change its iterators while it is being consumed.
+
+use std::marker::PhantomData;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex, MutexGuard, OnceLock, PoisonError};
+
+use crate::Marker;
+use crate::altern::SourceId;
+
+/// A change requested through a `VecAlternHandle`, applied by the `VecAltern` before its next element.
+pub(super) enum Command<I> {
+    Push(I, usize),
+    Pause(SourceId, PendingResult),
+    Resume(SourceId, PendingResult),
+    Remove(SourceId, PendingResult),
+}
+
+impl<I> Command<I> {
+    /// Returns the same command with the iterator mapped by `f`, whose result is not reported to the handle, to
+    /// apply it to a copy of the `VecAltern`.
+    pub(super) fn map<J>(&self, f: impl FnOnce(&I) -> J) -> Command<J> {
+        match self {
+            Command::Push(iterator, weight) => Command::Push(f(iterator), *weight),
+            Command::Pause(id, _) => Command::Pause(*id, PendingResult::default()),
+            Command::Resume(id, _) => Command::Resume(*id, PendingResult::default()),
+            Command::Remove(id, _) => Command::Remove(*id, PendingResult::default()),
+        }
+    }
+}
+
+/// The result of a change requested through a `VecAlternHandle`, known once the `VecAltern` applies it.
+///
+/// The handle cannot tell right away whether an iterator was running or paused: the `VecAltern` may be in the middle
+/// of computing an element, and it applies the changes only before the next one, to keep its order consistent.
+///
+/// # Example
+/// ```
+/// use combin_iterator::altern::VecAltern;
+/// let mut iter = VecAltern::new();
+/// iter.add(0..2);
+/// let second = iter.add(10..12);
+/// let handle = iter.handle();
+///
+/// let paused = handle.pause(second);
+/// let paused_again = handle.pause(second);
+/// assert_eq!(paused.get(), None);
+/// assert_eq!(iter.next(), Some(0));
+/// assert_eq!((paused.get(), paused_again.get()), (Some(true), Some(false)));
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct PendingResult(Arc<OnceLock<bool>>);
+
+impl PendingResult {
+    /// Returns what the method of `VecAltern` returned when the change was applied, or `None` if it is not applied
+    /// yet (or never will be, if the `VecAltern` was dropped first).
+    pub fn get(&self) -> Option<bool> {
+        self.0.get().copied()
+    }
+
+    /// Reports the result of the change.
+    pub(super) fn set(&self, result: bool) {
+        let _ = self.0.set(result);
+    }
+}
+
+/// The commands waiting to be applied, and the id of the next iterator to add.
+pub(super) struct Commands<I> {
+    pub(super) pending: Vec<Command<I>>,
+    pub(super) next_id: usize,
+}
+
+/// The state shared by a `VecAltern` and its handles.
+///
+/// `dirty` tells, without locking, whether there are commands waiting.
+pub(super) struct Shared<I> {
+    pub(super) dirty: AtomicBool,
+    pub(super) commands: Mutex<Commands<I>>,
+}
+
+impl<I> Shared<I> {
+    pub(super) fn new(next_id: usize) -> Self {
+        Self {
+            dirty: AtomicBool::new(false),
+            commands: Mutex::new(Commands { pending: Vec::new(), next_id }),
+        }
+    }
+
+    /// Locks the commands. A panic while they were locked cannot leave them inconsistent, so poisoning is ignored.
+    pub(super) fn lock(&self) -> MutexGuard<'_, Commands<I>> {
+        self.commands.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+/// A handle to add, pause, resume or remove the iterators of a `VecAltern`, created by `VecAltern::handle`.
+///
+/// The handle can be cloned, and used while the `VecAltern` is consumed, by a `for` loop or an adapter.
+/// The changes are applied in the order they were requested, when the `VecAltern` computes its next element
+/// (from the front or the back), but `size_hint` and `len` already take them into account.<br/>
+/// If the iterators are `Send`, so is the handle, and it can be used from another thread.
+pub struct VecAlternHandle<'a, A, I = Box<dyn Iterator<Item = A> + 'a>> {
+    shared: Arc<Shared<I>>,
+    _marker: Marker<'a, A>,
+}
+
+impl<'a, A, I> VecAlternHandle<'a, A, I> {
+    pub(super) fn new(shared: Arc<Shared<I>>) -> Self {
+        Self { shared, _marker: PhantomData }
+    }
+
+    /// Requests a change, which the `VecAltern` will apply before its next element, and returns its result.
+    fn send(&self, command: impl FnOnce(PendingResult) -> Command<I>) -> PendingResult {
+        let result = PendingResult::default();
+        let mut commands = self.shared.lock();
+        commands.pending.push(command(result.clone()));
+        self.shared.dirty.store(true, Ordering::Release);
+        result
+    }
+}
+
+impl<'a, A> VecAlternHandle<'a, A> {
+    /// Adds an iterator, like `VecAltern::add`.
+    pub fn add(&self, iterator: impl Iterator<Item = A> + 'a) -> SourceId {
+        self.push(Box::new(iterator))
+    }
+
+    /// Adds an iterator with a weight, like `VecAltern::add_weighted`.
+    pub fn add_weighted(&self, iterator: impl Iterator<Item = A> + 'a, weight: usize) -> SourceId {
+        self.push_weighted(Box::new(iterator), weight)
+    }
+}
+
+impl<'a, A, I> VecAlternHandle<'a, A, I>
+where
+    I: Iterator<Item = A>
+{
+    /// Adds an iterator of the stored type, like `VecAltern::push`.
+    pub fn push(&self, iterator: I) -> SourceId {
+        self.push_weighted(iterator, 1)
+    }
+
+    /// Adds an iterator of the stored type with a weight, like `VecAltern::push_weighted`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `weight` is 0.
+    pub fn push_weighted(&self, iterator: I, weight: usize) -> SourceId {
+        assert!(weight > 0, "the weight of an iterator must be at least 1");
+        let mut commands = self.shared.lock();
+        let id = SourceId(commands.next_id);
+        commands.next_id += 1;
+        commands.pending.push(Command::Push(iterator, weight));
+        self.shared.dirty.store(true, Ordering::Release);
+        id
+    }
+
+    /// Pauses an iterator, like `VecAltern::pause`.
+    ///
+    /// Whether the iterator was running is known once the change is applied (see `PendingResult`).
+    pub fn pause(&self, id: SourceId) -> PendingResult {
+        self.send(|result| Command::Pause(id, result))
+    }
+
+    /// Resumes a paused iterator, like `VecAltern::resume`.
+    ///
+    /// Whether the iterator was paused is known once the change is applied (see `PendingResult`).
+    pub fn resume(&self, id: SourceId) -> PendingResult {
+        self.send(|result| Command::Resume(id, result))
+    }
+
+    /// Removes an iterator, like `VecAltern::remove`.
+    ///
+    /// Whether the iterator was running or paused is known once the change is applied (see `PendingResult`).
+    pub fn remove(&self, id: SourceId) -> PendingResult {
+        self.send(|result| Command::Remove(id, result))
+    }
+}
+
+impl<'a, A, I> Clone for VecAlternHandle<'a, A, I> {
+    fn clone(&self) -> Self {
+        Self::new(self.shared.clone())
+    }
+}
@@ -0,0 +1,19 @@
+//! This module contains some structure to enumerate fairly the combinations of infinite iterators
+//!
+//! `VecAltern` takes one element of each iterator per round, so it reaches every element of infinitely many
+//! elements in finitely many steps, but a cartesian product (see the `product` module) never reaches the second
+//! element of the first iterator if the last one is infinite. The structures of this module enumerate them along
+//! diagonals instead, so every combination is reached after finitely many steps.
+//!
+//! ## When to use what ?
+//!
+//! - `VecDiagonal` enumerates the product of several possibly infinite iterators, by increasing sum of the positions
+//!   of the elements in their iterator (Cantor's order). The `diagonal!` macro builds one.
+//! - `Dovetail` flattens a possibly infinite iterator of possibly infinite iterators: each round takes one element
+//!   of each active inner iterator, then activates the next inner iterator.
+
+pub mod dovetail;
+pub mod vec_diagonal;
+
+pub use dovetail::Dovetail;
+pub use vec_diagonal::VecDiagonal;
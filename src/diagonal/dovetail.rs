@@ -0,0 +1,167 @@
+//! # Dovetail
+//!
+//! The `dovetail` module provides an iterator, `Dovetail`, for flattening a possibly infinite iterator of possibly
+//! infinite iterators, so every element is reached after finitely many steps.
+//!
+//! ## Usage
+//!
+//! To use `Dovetail`, create a new instance with `Dovetail::new(iterator_of_iterators)`.
+//! Each round activates the next inner iterator, then the `next` method yields one element of each active inner
+//! iterator, in the order they were activated. The exhausted inner iterators are dropped at the end of their round.
+//!
+//! ## Examples
+//!
+//! ```rust
+//! use combin_iterator::diagonal::Dovetail;
+//! // The multiples of each number, which would never reach the multiples of 2 with `flatten`.
+//! let iter = Dovetail::new((1..).map(|n| (1..).map(move |k| n * k)));
+//! assert_eq!(iter.take(10).collect::<Vec<_>>(), vec![1, 2, 2, 3, 4, 3, 4, 6, 6, 4]);
+//!
+//! let iter = Dovetail::new(vec![vec![1, 2, 3], vec![4], vec![5, 6]]);
+//! assert_eq!(iter.collect::<Vec<_>>(), vec![1, 2, 4, 3, 5, 6]);
+//! ```
+//!
+//! ## Notes
+//!
+//! - Like `flatten`, `next` does not return while the outer iterator yields empty inner iterators, so it never
+//!   returns if all of the infinitely many inner iterators are empty.
+
+/// Struct to flatten a possibly infinite iterator of possibly infinite iterators fairly
+pub struct Dovetail<O>
+where
+    O: Iterator,
+    O::Item: IntoIterator
+{
+    /// The iterator of iterators, `None` once exhausted.
+    outer: Option<O>,
+    /// The active inner iterators, in the order they were activated.
+    active: Vec<<O::Item as IntoIterator>::IntoIter>,
+    /// The index in `active` of the next inner iterator of the round.
+    position: usize,
+    /// The number of inner iterators of the round still active, moved in order before the exhausted ones, which
+    /// are dropped at the end of the round so no exhausted iterator is removed from the middle of `active`.
+    kept: usize,
+}
+
+impl<O> Dovetail<O>
+where
+    O: Iterator,
+    O::Item: IntoIterator
+{
+    /// Creates a new instance of a `Dovetail` iterator.
+    pub fn new(iterators: impl IntoIterator<IntoIter = O>) -> Self {
+        Self { outer: Some(iterators.into_iter()), active: Vec::new(), position: 0, kept: 0 }
+    }
+}
+
+impl<O> Iterator for Dovetail<O>
+where
+    O: Iterator,
+    O::Item: IntoIterator
+{
+    type Item = <O::Item as IntoIterator>::Item;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if self.position >= self.active.len() {
+                self.active.truncate(self.kept);
+                self.position = 0;
+                self.kept = 0;
+                match self.outer.as_mut().and_then(Iterator::next) {
+                    Some(inner) => self.active.push(inner.into_iter()),
+                    None if self.active.is_empty() => {
+                        self.outer = None;
+                        return None;
+                    }
+                    None => self.outer = None,
+                }
+            }
+            match self.active[self.position].next() {
+                Some(value) => {
+                    self.active.swap(self.kept, self.position);
+                    self.kept += 1;
+                    self.position += 1;
+                    return Some(value);
+                }
+                None => self.position += 1,
+            }
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (lower, upper) = self.active[..self.kept].iter().chain(&self.active[self.position..]).map(Iterator::size_hint).fold((0, Some(0)), |(lower, upper): (usize, Option<usize>), hint| {
+            (lower.saturating_add(hint.0), upper.zip(hint.1).and_then(|(u1, u2)| u1.checked_add(u2)))
+        });
+        match &self.outer {
+            Some(outer) if outer.size_hint().1 != Some(0) => (lower, None),
+            _ => (lower, upper),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Dovetail;
+
+    #[test]
+    fn rounds() {
+        let iter = Dovetail::new((0..).map(|n| (0..).map(move |k| (n, k))));
+        assert_eq!(iter.take(10).collect::<Vec<_>>(), vec![
+            (0, 0),
+            (0, 1), (1, 0),
+            (0, 2), (1, 1), (2, 0),
+            (0, 3), (1, 2), (2, 1), (3, 0),
+        ]);
+    }
+
+    #[test]
+    fn reaches_every_element() {
+        let mut iter = Dovetail::new((0..).map(|n| (0..).map(move |k| (n, k))));
+        assert_eq!(iter.position(|pair| pair == (3, 4)), Some(31));
+    }
+
+    #[test]
+    fn finite() {
+        let iter = Dovetail::new(vec![vec![], vec![1, 2, 3], vec![], vec![4], vec![5, 6, 7]]);
+        assert_eq!(iter.collect::<Vec<_>>(), vec![1, 2, 3, 4, 5, 6, 7]);
+
+        let iter = Dovetail::new(Vec::<Vec<i32>>::new());
+        assert_eq!(iter.count(), 0);
+    }
+
+    #[test]
+    fn many_short() {
+        let iter = Dovetail::new((0..1000).map(|n| n..n + 1 + n % 3));
+        let values = iter.collect::<Vec<_>>();
+
+        // Plain rounds, removing the exhausted iterators one by one.
+        let mut expected = Vec::new();
+        let mut active = Vec::new();
+        for inner in (0..1000).map(|n| n..n + 1 + n % 3).map(Some).chain(std::iter::repeat(None)) {
+            active.extend(inner);
+            active.retain_mut(|inner| inner.next().map(|value| expected.push(value)).is_some());
+            if active.is_empty() {
+                break;
+            }
+        }
+        assert_eq!(values[..8], [0, 1, 2, 2, 3, 3, 4, 4]);
+        assert_eq!(values, expected);
+    }
+
+    #[test]
+    fn infinite_outer_of_finite() {
+        let iter = Dovetail::new((0..).map(|n| 0..n % 3));
+        assert_eq!(iter.take(8).collect::<Vec<_>>(), vec![0, 0, 1, 0, 0, 1, 0, 0]);
+    }
+
+    #[test]
+    fn size_hint() {
+        let mut iter = Dovetail::new(vec![vec![1, 2], vec![3]]);
+        assert_eq!(iter.size_hint(), (0, None));
+        iter.next();
+        assert_eq!(iter.size_hint(), (1, None));
+        iter.next();
+        assert_eq!(iter.size_hint(), (1, Some(1)));
+        assert_eq!(iter.count(), 1);
+    }
+}
@@ -0,0 +1,336 @@
+//! # VecDiagonal
+//!
+//! The `vec_diagonal` module provides an iterator, `VecDiagonal`, for enumerating the product of several possibly
+//! infinite iterators in diagonal order.
+//!
+//! ## Usage
+//!
+//! To use `VecDiagonal`, create a new instance with `VecDiagonal::new()` and add iterators using the `add` method
+//! (or `add_and` for a build pattern), or use the macro `diagonal`.
+//! The `next` method will then yield a `Vec` of one element of each iterator, in the order they were added, for
+//! every combination of their elements. The combinations are ordered by the sum of the positions of their elements
+//! in their iterator, then in lexicographic order, so each of them is reached after finitely many steps, even if all
+//! the iterators are infinite.
+//!
+//! ## Examples
+//!
+//! ```rust
+//! use combin_iterator::diagonal::VecDiagonal;
+//! let iter = VecDiagonal::new().add_and(0..).add_and(0..);
+//! assert_eq!(iter.take(6).collect::<Vec<_>>(), vec![
+//!     vec![0, 0],
+//!     vec![0, 1], vec![1, 0],
+//!     vec![0, 2], vec![1, 1], vec![2, 0],
+//! ]);
+//!
+//! // The finite iterators are skipped once exhausted:
+//! let iter = VecDiagonal::from(vec![0..2, 0..3]);
+//! assert_eq!(iter.collect::<Vec<_>>(), vec![
+//!     vec![0, 0], vec![0, 1], vec![1, 0], vec![0, 2], vec![1, 1], vec![1, 2],
+//! ]);
+//! ```
+//!
+//! ## Notes
+//!
+//! - Every element taken from an iterator is kept, to be combined with the elements of the other iterators, so the
+//!   elements must be `Clone`, and the memory grows with the number of elements taken.
+//! - A `VecDiagonal` without iterators yields nothing, and so does a product with an empty iterator.
+//! - Adding an iterator restarts the enumeration from its first combination.
+
+use std::marker::PhantomData;
+
+use crate::Marker;
+
+/// Struct to enumerate the product of several possibly infinite iterators in diagonal order
+///
+/// An iterator is only advanced when the diagonal reaches its next element, so the elements are taken in the order
+/// of the diagonals, and kept for the combinations to come. `I` is the type of the iterators, boxed by default (see
+/// the module `altern`).
+pub struct VecDiagonal<'a, A, I = Box<dyn Iterator<Item = A> + 'a>>
+where
+    I: Iterator<Item = A>
+{
+    /// The iterators, `None` once exhausted.
+    iters: Vec<Option<I>>,
+    /// The elements taken from each iterator.
+    seen: Vec<Vec<A>>,
+    /// The position in its iterator of each element of the current combination.
+    positions: Vec<usize>,
+    /// The sum of `positions`, i.e. the current diagonal.
+    sum: usize,
+    started: bool,
+    done: bool,
+    yielded: usize,
+    _marker: Marker<'a, A>,
+}
+
+impl<'a, A: Clone> VecDiagonal<'a, A> {
+    /// Creates a new instance of a `VecDiagonal` iterator.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates an empty `VecDiagonal` with room for `capacity` iterators, the dimensions of the product.
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self::from(Vec::with_capacity(capacity))
+    }
+
+    /// Adds an iterator to the `VecDiagonal` instance.
+    ///
+    /// # Returns
+    ///
+    /// The updated `VecDiagonal` instance with the added iterator, to use like a builder.
+    pub fn add_and(mut self, iterator: impl Iterator<Item = A> + 'a) -> Self {
+        self.push(Box::new(iterator));
+        self
+    }
+
+    /// Adds an iterator to the `VecDiagonal` instance.
+    ///
+    /// The enumeration restarts from its first combination.
+    pub fn add(&mut self, iterator: impl Iterator<Item = A> + 'a) {
+        self.push(Box::new(iterator));
+    }
+}
+
+impl<'a, A, I> VecDiagonal<'a, A, I>
+where
+    A: Clone,
+    I: Iterator<Item = A>
+{
+    /// Adds an iterator of the stored type, without boxing it.
+    ///
+    /// The enumeration restarts from its first combination.
+    pub fn push(&mut self, iterator: I) {
+        self.iters.push(Some(iterator));
+        self.seen.push(Vec::new());
+        self.positions.push(0);
+        self.sum = 0;
+        self.started = false;
+        self.done = false;
+        self.yielded = 0;
+    }
+
+    /// Returns the number of elements of the iterator `index`, or `usize::MAX` if it is not known yet.
+    fn bound(&self, index: usize) -> usize {
+        match self.iters[index] {
+            Some(_) => usize::MAX,
+            None => self.seen[index].len(),
+        }
+    }
+
+    /// Sets the positions from `from` to the lexicographically smallest ones completing the current diagonal, i.e.
+    /// the largest possible positions at the end.
+    ///
+    /// Returns `false` if the positions before `from` leave no way to complete it.
+    fn fill(&mut self, from: usize) -> bool {
+        let mut left = self.sum - self.positions[..from].iter().sum::<usize>();
+        for index in (from..self.positions.len()).rev() {
+            let position = left.min(self.bound(index) - 1);
+            self.positions[index] = position;
+            left -= position;
+        }
+        left == 0
+    }
+
+    /// Moves to the next combination of the current diagonal, where all the positions before the first one out of
+    /// the bounds are kept.
+    fn advance(&mut self) -> bool {
+        let count = self.positions.len();
+        let valid = (0..count).find(|&index| self.positions[index] >= self.bound(index)).unwrap_or(count);
+        for index in (0..valid.min(count - 1)).rev() {
+            if self.positions[index] + 1 < self.bound(index) && self.positions[..=index].iter().sum::<usize>() < self.sum {
+                self.positions[index] += 1;
+                if self.fill(index + 1) {
+                    return true;
+                }
+            }
+        }
+        false
+    }
+
+    /// Moves to the next candidate combination, returns `false` if there is none.
+    fn step(&mut self) -> bool {
+        let count = self.positions.len();
+        if count == 0 || (0..count).any(|index| self.bound(index) == 0) {
+            return false;
+        }
+        if !self.started {
+            self.started = true;
+            return self.fill(0);
+        }
+        if self.advance() {
+            return true;
+        }
+        let last = (0..count).try_fold(0usize, |last, index| {
+            self.iters[index].is_none().then(|| last + self.seen[index].len() - 1)
+        });
+        self.sum += 1;
+        last.is_none_or(|last| self.sum <= last) && self.fill(0)
+    }
+
+    /// Returns the current combination, taking the missing elements from the iterators, or `None` if one of them is
+    /// exhausted before.
+    fn fetch(&mut self) -> Option<Vec<A>> {
+        for (index, &position) in self.positions.iter().enumerate() {
+            while self.seen[index].len() <= position {
+                match self.iters[index].as_mut().and_then(Iterator::next) {
+                    Some(value) => self.seen[index].push(value),
+                    None => {
+                        self.iters[index] = None;
+                        return None;
+                    }
+                }
+            }
+        }
+        Some(self.positions.iter().zip(&self.seen).map(|(&position, seen)| seen[position].clone()).collect())
+    }
+}
+
+impl<'a, A, I> Default for VecDiagonal<'a, A, I>
+where
+    A: Clone,
+    I: Iterator<Item = A>
+{
+    fn default() -> Self {
+        Self::from(Vec::new())
+    }
+}
+
+impl<'a, A, I> From<Vec<I>> for VecDiagonal<'a, A, I>
+where
+    A: Clone,
+    I: Iterator<Item = A>
+{
+    /// Creates a `VecDiagonal` over iterators of one concrete type, without boxing them.
+    fn from(iters: Vec<I>) -> Self {
+        let count = iters.len();
+        Self {
+            iters: iters.into_iter().map(Some).collect(),
+            seen: (0..count).map(|_| Vec::new()).collect(),
+            positions: vec![0; count],
+            sum: 0,
+            started: false,
+            done: false,
+            yielded: 0,
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<'a, A, I> Iterator for VecDiagonal<'a, A, I>
+where
+    A: Clone,
+    I: Iterator<Item = A>
+{
+    type Item = Vec<A>;
+
+    fn next(&mut self) -> Option<Vec<A>> {
+        while !self.done {
+            if !self.step() {
+                self.done = true;
+                break;
+            }
+            if let Some(combination) = self.fetch() {
+                self.yielded += 1;
+                return Some(combination);
+            }
+        }
+        None
+    }
+
+    /// The number of combinations of all the elements, seen or not, minus the ones already yielded.
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        if self.done || self.iters.is_empty() {
+            return (0, Some(0));
+        }
+        let (lower, upper) = self.iters.iter().zip(&self.seen).fold((1, Some(1)), |(lower, upper): (usize, Option<usize>), (iter, seen)| {
+            let (left_lower, left_upper) = iter.as_ref().map_or((0, Some(0)), Iterator::size_hint);
+            let total = (seen.len().saturating_add(left_lower), left_upper.and_then(|left| left.checked_add(seen.len())));
+            (lower.saturating_mul(total.0), upper.zip(total.1).and_then(|(u1, u2)| u1.checked_mul(u2)))
+        });
+        (lower.saturating_sub(self.yielded), upper.map(|upper| upper.saturating_sub(self.yielded)))
+    }
+}
+
+impl<'a, A, I> ExactSizeIterator for VecDiagonal<'a, A, I>
+where
+    A: Clone,
+    I: ExactSizeIterator<Item = A> {}
+
+#[cfg(test)]
+mod tests {
+    use super::VecDiagonal;
+
+    #[test]
+    fn infinite() {
+        let iter = VecDiagonal::new().add_and(0..).add_and(0..).add_and(0..);
+        assert_eq!(iter.take(10).collect::<Vec<_>>(), vec![
+            vec![0, 0, 0],
+            vec![0, 0, 1], vec![0, 1, 0], vec![1, 0, 0],
+            vec![0, 0, 2], vec![0, 1, 1], vec![0, 2, 0], vec![1, 0, 1], vec![1, 1, 0], vec![2, 0, 0],
+        ]);
+    }
+
+    #[test]
+    fn reaches_every_combination() {
+        let mut iter = VecDiagonal::new().add_and(0..).add_and((0..).map(|x| x * 10)).add_and(0..);
+        let position = iter.position(|combination| combination == vec![3, 20, 1]);
+        assert_eq!(position, Some(76));
+    }
+
+    #[test]
+    fn finite() {
+        for lens in [vec![1], vec![3, 1], vec![2, 3, 2], vec![1, 4, 1, 3], vec![5, 2]] {
+            let iter = VecDiagonal::from(lens.iter().map(|&len| 0..len).collect::<Vec<_>>());
+            let mut expected = Vec::new();
+            for combination in crate::product::VecProduct::from(lens.iter().map(|&len| 0..len).collect::<Vec<_>>()) {
+                expected.push(combination);
+            }
+            expected.sort_by_key(|combination| combination.iter().sum::<usize>());
+            assert_eq!(iter.len(), expected.len());
+            assert_eq!(iter.collect::<Vec<_>>(), expected);
+        }
+
+        // The combinations left are counted from the elements taken and the ones left in the iterators.
+        let mut iter = VecDiagonal::from(vec![0..3, 0..4]);
+        for len in (0..=12).rev() {
+            assert_eq!(iter.len(), len);
+            iter.next();
+        }
+        assert_eq!(iter.next(), None);
+    }
+
+    #[test]
+    fn finite_and_infinite() {
+        let iter = VecDiagonal::new().add_and(0..2).add_and(0..);
+        assert_eq!(iter.take(7).collect::<Vec<_>>(), vec![
+            vec![0, 0], vec![0, 1], vec![1, 0], vec![0, 2], vec![1, 1], vec![0, 3], vec![1, 2],
+        ]);
+
+        let iter = VecDiagonal::new().add_and((0..).filter(|x| x % 2 == 0)).add_and(0..1);
+        assert_eq!(iter.size_hint(), (0, None));
+        assert_eq!(iter.take(3).collect::<Vec<_>>(), vec![vec![0, 0], vec![2, 0], vec![4, 0]]);
+
+        let iter = VecDiagonal::new().add_and(0..3).add_and(0..);
+        assert_eq!(iter.size_hint(), (usize::MAX, None));
+    }
+
+    #[test]
+    fn empty() {
+        let iter = VecDiagonal::<i32>::new();
+        assert_eq!(iter.size_hint(), (0, Some(0)));
+        assert_eq!(iter.count(), 0);
+
+        let iter = VecDiagonal::new().add_and(0..).add_and(std::iter::empty());
+        assert_eq!(iter.count(), 0);
+    }
+
+    #[test]
+    fn add_restarts() {
+        let mut iter = VecDiagonal::new().add_and(0..2);
+        assert_eq!(iter.next(), Some(vec![0]));
+        iter.add(5..7);
+        assert_eq!(iter.collect::<Vec<_>>(), vec![vec![0, 5], vec![0, 6], vec![1, 5], vec![1, 6]]);
+    }
+}
@@ -0,0 +1,412 @@
+//! This module contains some structure to enumerate all the interleavings of finite sequences
+//!
+//! An alternation yields one interleaving of its iterators, in which they take turns. `Interleavings` yields all of
+//! them instead: every way to merge the sequences into one while keeping the order of each sequence (their shuffle
+//! product), like all the possible schedules of concurrent threads.
+//!
+//! ## Usage
+//!
+//! To use `Interleavings`, create a new instance with `Interleavings::new()` and add sequences using the `add`
+//! method (or `add_and` for a build pattern), or use the macro `interleavings`, which takes the same iterators as
+//! `altern!`. The sequences are collected when they are added, so they must be finite.<br/>
+//! The `next` method will then yield each interleaving as a `Vec`, in lexicographic order of the sources of their
+//! elements. `schedules()` yields these sources instead (the index of the sequence of each element, in the order
+//! they were added), to replay the interleaving on something else than the elements.
+//!
+//! The number of interleavings is the multinomial coefficient `(n1 + n2 + ...)! / (n1! n2! ...)`, which quickly
+//! overflows: `count_exact` returns the number of interleavings left, or `None` if it overflows.
+//! `with_max_switches(s)` only keeps the interleavings switching at most `s` times from a sequence to another, like a
+//! bound on the number of context switches. Their number is only counted on the first call to `count_exact`, as it
+//! can take long, and `size_hint` is exact once it is counted.
+//!
+//! ## Examples
+//!
+//! ```rust
+//! use combin_iterator::interleavings::Interleavings;
+//! let iter = Interleavings::new().add_and(vec!['a', 'b']).add_and(vec!['x']);
+//! assert_eq!(iter.count_exact(), Some(3));
+//! assert_eq!(iter.map(String::from_iter).collect::<Vec<_>>(), vec!["abx", "axb", "xab"]);
+//!
+//! let iter = Interleavings::new().add_and(vec!['a', 'b']).add_and(vec!['x', 'y']);
+//! assert_eq!(iter.count_exact(), Some(6));
+//! let schedules = iter.with_max_switches(1).schedules();
+//! assert_eq!(schedules.collect::<Vec<_>>(), vec![vec![0, 0, 1, 1], vec![1, 1, 0, 0]]);
+//! ```
+//!
+//! ## Notes
+//!
+//! - The elements must be `Clone`, as each of them is part of every interleaving.
+//! - Without sequences, or with empty ones only, there is a single interleaving, which is empty.
+//! - Adding a sequence or bounding the switches restarts the enumeration from the first interleaving.
+
+use std::cell::OnceCell;
+use std::collections::HashMap;
+
+/// Struct to enumerate all the interleavings of several finite sequences
+pub struct Interleavings<A> {
+    seqs: Vec<Vec<A>>,
+    schedules: Schedules,
+}
+
+impl<A: Clone> Interleavings<A> {
+    /// Creates a new instance of an `Interleavings` iterator.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates an empty `Interleavings` with room for `capacity` sequences.
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self::from(Vec::with_capacity(capacity))
+    }
+
+    /// Adds a sequence to the `Interleavings` instance.
+    ///
+    /// # Returns
+    ///
+    /// The updated `Interleavings` instance with the added sequence, to use like a builder.
+    pub fn add_and(mut self, sequence: impl IntoIterator<Item = A>) -> Self {
+        self.add(sequence);
+        self
+    }
+
+    /// Adds a sequence to the `Interleavings` instance, collecting it.
+    ///
+    /// The enumeration restarts from the first interleaving.
+    pub fn add(&mut self, sequence: impl IntoIterator<Item = A>) {
+        self.seqs.push(sequence.into_iter().collect());
+        self.schedules = Schedules::new(self.lens(), self.schedules.max_switches);
+    }
+
+    /// Only keeps the interleavings switching at most `switches` times from a sequence to another.
+    ///
+    /// The enumeration restarts from the first interleaving.
+    ///
+    /// # Example
+    /// ```
+    /// use combin_iterator::interleavings::Interleavings;
+    /// let iter = Interleavings::new().add_and(1..3).add_and(3..5).with_max_switches(2);
+    /// assert_eq!(iter.collect::<Vec<_>>(), vec![vec![1, 2, 3, 4], vec![1, 3, 4, 2], vec![3, 1, 2, 4], vec![3, 4, 1, 2]]);
+    /// ```
+    pub fn with_max_switches(mut self, switches: usize) -> Self {
+        self.schedules = Schedules::new(self.lens(), Some(switches));
+        self
+    }
+
+    /// Returns the number of interleavings left, or `None` if it overflows.
+    ///
+    /// With a bound on the switches, the first call counts them, which can take long.
+    pub fn count_exact(&self) -> Option<usize> {
+        self.schedules.count_exact()
+    }
+
+    /// Yields the interleavings as the index of the sequence of each of their elements, in the same order.
+    pub fn schedules(self) -> Schedules {
+        self.schedules
+    }
+
+    fn lens(&self) -> Vec<usize> {
+        self.seqs.iter().map(Vec::len).collect()
+    }
+}
+
+impl<A: Clone> Default for Interleavings<A> {
+    fn default() -> Self {
+        Self::from(Vec::new())
+    }
+}
+
+impl<A: Clone> From<Vec<Vec<A>>> for Interleavings<A> {
+    fn from(seqs: Vec<Vec<A>>) -> Self {
+        let schedules = Schedules::new(seqs.iter().map(Vec::len).collect(), None);
+        Self { seqs, schedules }
+    }
+}
+
+impl<A: Clone> Iterator for Interleavings<A> {
+    type Item = Vec<A>;
+
+    fn next(&mut self) -> Option<Vec<A>> {
+        let schedule = self.schedules.next()?;
+        let mut positions = vec![0; self.seqs.len()];
+        Some(schedule.into_iter().map(|source| {
+            positions[source] += 1;
+            self.seqs[source][positions[source] - 1].clone()
+        }).collect())
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.schedules.size_hint()
+    }
+}
+
+/// Struct to enumerate the interleavings of several finite sequences as the index of the sequence of each element,
+/// created by `Interleavings::schedules`.
+pub struct Schedules {
+    /// The number of elements of each sequence.
+    lens: Vec<usize>,
+    /// The current interleaving.
+    schedule: Vec<usize>,
+    /// The number of elements of each sequence not in `schedule` yet, while building it.
+    left: Vec<usize>,
+    /// The number of switches in `schedule` up to each position.
+    switches: Vec<usize>,
+    max_switches: Option<usize>,
+    started: bool,
+    done: bool,
+    /// The number of interleavings, `None` if it overflows, counted on demand.
+    total: OnceCell<Option<usize>>,
+    yielded: usize,
+}
+
+impl Schedules {
+    /// Creates the schedules of sequences of lengths `lens`, with at most `max_switches` switches.
+    pub fn new(lens: Vec<usize>, max_switches: Option<usize>) -> Self {
+        let len = lens.iter().sum();
+        Self {
+            left: lens.clone(),
+            lens,
+            schedule: vec![0; len],
+            switches: vec![0; len],
+            max_switches,
+            started: false,
+            done: false,
+            total: OnceCell::new(),
+            yielded: 0,
+        }
+    }
+
+    /// Returns the number of schedules left, or `None` if it overflows.
+    ///
+    /// With a bound on the switches, the first call counts them, which can take long.
+    pub fn count_exact(&self) -> Option<usize> {
+        let total = self.total.get_or_init(|| match self.max_switches {
+            None => multinomial(&self.lens),
+            Some(max_switches) => count(&mut self.lens.clone(), None, max_switches, &mut HashMap::new()),
+        });
+        total.map(|total| total - self.yielded)
+    }
+
+    /// Returns whether the sequences left can complete the schedule after `last`, with `used` switches so far.
+    fn completes(&self, last: Option<usize>, used: usize) -> bool {
+        let Some(max_switches) = self.max_switches else {
+            return true;
+        };
+        let sources = self.left.iter().filter(|&&left| left > 0).count();
+        // The first of the sources left is free if it is the first element, or if it continues `last`.
+        let free = sources > 0 && last.is_none_or(|last| self.left[last] > 0);
+        used + sources - free as usize <= max_switches
+    }
+
+    /// Returns whether the next element can come from `source`, after `last` with `used` switches so far.
+    fn fits(&mut self, source: usize, last: Option<usize>, used: usize) -> bool {
+        if self.left[source] == 0 {
+            return false;
+        }
+        self.left[source] -= 1;
+        let fits = self.completes(Some(source), used + switch(last, source));
+        self.left[source] += 1;
+        fits
+    }
+
+    /// Returns the source of the element before `position`, and the number of switches up to it.
+    fn before(&self, position: usize) -> (Option<usize>, usize) {
+        match position.checked_sub(1) {
+            Some(before) => (Some(self.schedule[before]), self.switches[before]),
+            None => (None, 0),
+        }
+    }
+
+    fn place(&mut self, position: usize, source: usize) {
+        let (last, used) = self.before(position);
+        self.schedule[position] = source;
+        self.switches[position] = used + switch(last, source);
+        self.left[source] -= 1;
+    }
+
+    /// Completes the schedule from `from` with the smallest sources which can complete it.
+    fn fill(&mut self, from: usize) {
+        for position in from..self.schedule.len() {
+            let (last, used) = self.before(position);
+            let source = (0..self.lens.len()).find(|&source| self.fits(source, last, used))
+                .expect("a schedule which can be completed has a next source");
+            self.place(position, source);
+        }
+    }
+
+    /// Moves to the next schedule, returns `false` if there is none.
+    fn step(&mut self) -> bool {
+        if !self.started {
+            self.started = true;
+            if !self.completes(None, 0) {
+                return false;
+            }
+            self.fill(0);
+            return true;
+        }
+        for position in (0..self.schedule.len()).rev() {
+            let old = self.schedule[position];
+            self.left[old] += 1;
+            let (last, used) = self.before(position);
+            if let Some(source) = (old + 1..self.lens.len()).find(|&source| self.fits(source, last, used)) {
+                self.place(position, source);
+                self.fill(position + 1);
+                return true;
+            }
+        }
+        false
+    }
+}
+
+impl Iterator for Schedules {
+    type Item = Vec<usize>;
+
+    fn next(&mut self) -> Option<Vec<usize>> {
+        if self.done || !self.step() {
+            self.done = true;
+            return None;
+        }
+        self.yielded += 1;
+        Some(self.schedule.clone())
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        if self.max_switches.is_some() && self.total.get().is_none() {
+            // Not counted yet: the interleavings without bound are an upper bound.
+            return (0, multinomial(&self.lens).map(|total| total - self.yielded));
+        }
+        match self.count_exact() {
+            Some(left) => (left, Some(left)),
+            None => (usize::MAX, None),
+        }
+    }
+}
+
+/// Returns 1 if going from `last` to `source` is a switch.
+fn switch(last: Option<usize>, source: usize) -> usize {
+    last.is_some_and(|last| last != source) as usize
+}
+
+/// Returns `(n1 + n2 + ...)! / (n1! n2! ...)`, or `None` if it overflows.
+fn multinomial(lens: &[usize]) -> Option<usize> {
+    let mut result = 1usize;
+    let mut len = 0;
+    for &n in lens {
+        // Multiplies by the binomial `(len + n) choose n`, one factor at a time, each step being exact.
+        for k in 1..=n {
+            len += 1;
+            result = usize::try_from(result as u128 * len as u128 / k as u128).ok()?;
+        }
+    }
+    Some(result)
+}
+
+/// The number of schedules already counted, by elements left, last source and switches left.
+type Memo = HashMap<(Vec<usize>, Option<usize>, usize), Option<usize>>;
+
+/// Returns the number of schedules of the elements `left`, after `last`, with at most `switches` switches, or
+/// `None` if it overflows.
+fn count(
+    left: &mut Vec<usize>,
+    last: Option<usize>,
+    switches: usize,
+    memo: &mut Memo,
+) -> Option<usize> {
+    if left.iter().all(|&left| left == 0) {
+        return Some(1);
+    }
+    if let Some(&count) = memo.get(&(left.clone(), last, switches)) {
+        return count;
+    }
+    let mut total = Some(0usize);
+    for source in 0..left.len() {
+        let switch = switch(last, source);
+        if left[source] == 0 || switch > switches {
+            continue;
+        }
+        left[source] -= 1;
+        let count = count(left, Some(source), switches - switch, memo);
+        left[source] += 1;
+        total = total.zip(count).and_then(|(total, count)| total.checked_add(count));
+    }
+    memo.insert((left.clone(), last, switches), total);
+    total
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{multinomial, Interleavings, Schedules};
+
+    #[test]
+    fn interleavings() {
+        let iter = Interleavings::new().add_and(vec![1, 2]).add_and(vec![3]);
+        assert_eq!(iter.collect::<Vec<_>>(), vec![vec![1, 2, 3], vec![1, 3, 2], vec![3, 1, 2]]);
+
+        let iter = Interleavings::new().add_and(0..2).add_and(2..4).add_and(4..6);
+        assert_eq!(iter.count_exact(), Some(90));
+        let all = iter.collect::<Vec<_>>();
+        let mut sorted = all.clone();
+        sorted.sort();
+        sorted.dedup();
+        assert_eq!(sorted.len(), 90);
+        assert!(all.iter().all(|interleaving| {
+            (0..3).all(|seq| interleaving.iter().filter(|&&x| x / 2 == seq).is_sorted())
+        }));
+    }
+
+    #[test]
+    fn empty() {
+        let iter = Interleavings::<i32>::new();
+        assert_eq!(iter.count_exact(), Some(1));
+        assert_eq!(iter.collect::<Vec<_>>(), vec![Vec::<i32>::new()]);
+
+        let iter = Interleavings::new().add_and(vec![1, 2]).add_and(vec![]);
+        assert_eq!(iter.collect::<Vec<_>>(), vec![vec![1, 2]]);
+    }
+
+    #[test]
+    fn max_switches() {
+        let iter = Interleavings::new().add_and("aa".chars()).add_and("bb".chars()).with_max_switches(1);
+        assert_eq!(iter.map(String::from_iter).collect::<Vec<_>>(), vec!["aabb", "bbaa"]);
+
+        let iter = Interleavings::new().add_and(0..2).add_and(2..4).add_and(4..5).with_max_switches(1);
+        assert_eq!(iter.size_hint(), (0, Some(30)));
+        assert_eq!(iter.count_exact(), Some(0));
+        assert_eq!(iter.size_hint(), (0, Some(0)));
+        assert_eq!(iter.count(), 0);
+    }
+
+    #[test]
+    fn count_matches_enumeration() {
+        for lens in [vec![3, 2], vec![2, 2, 2], vec![1, 3, 0, 2], vec![4, 1, 1]] {
+            let all = Schedules::new(lens.clone(), None).collect::<Vec<_>>();
+            for max_switches in 0..6 {
+                let schedules = Schedules::new(lens.clone(), Some(max_switches));
+                let expected = all.iter().filter(|schedule| {
+                    schedule.windows(2).filter(|pair| pair[0] != pair[1]).count() <= max_switches
+                }).cloned().collect::<Vec<_>>();
+                assert_eq!(schedules.count_exact(), Some(expected.len()));
+                assert_eq!(schedules.size_hint(), (expected.len(), Some(expected.len())));
+                assert_eq!(schedules.collect::<Vec<_>>(), expected);
+            }
+        }
+    }
+
+    #[test]
+    fn count_overflow() {
+        assert_eq!(multinomial(&[2, 2, 2]), Some(90));
+        assert_eq!(multinomial(&[30, 30]), Some(118264581564861424));
+        assert_eq!(multinomial(&[40, 40]), None);
+
+        let iter = Interleavings::from(vec![vec![0u8; 40], vec![0u8; 40]]);
+        assert_eq!(iter.count_exact(), None);
+        assert_eq!(iter.size_hint(), (usize::MAX, None));
+    }
+
+    #[test]
+    fn lazy_count() {
+        // Counting these interleavings would take long, but enumerating the first ones does not.
+        let mut iter = Interleavings::from(vec![vec![0u8; 8]; 6]).with_max_switches(6);
+        assert_eq!(iter.next(), Some(vec![0; 48]));
+        assert_eq!(iter.size_hint().0, 0);
+    }
+}
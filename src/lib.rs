@@ -1,6 +1,16 @@
+// The README is included as is, with the indentation of its list items.
+#![allow(clippy::doc_overindented_list_items)]
 #![doc = include_str!("../README.md")]
 #[warn(missing_docs)]
 pub mod altern;
+pub mod diagonal;
+pub mod interleavings;
+pub mod merge;
+pub mod product;
+pub mod zip;
 
 #[macro_use]
-mod macros;
\ No newline at end of file
+mod macros;
+
+/// Keeps `'a` and `A` used by the structures storing their iterators without boxing them, without requiring `A: 'a`.
+pub(crate) type Marker<'a, A> = std::marker::PhantomData<(&'a (), fn() -> A)>;
\ No newline at end of file
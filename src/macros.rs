@@ -36,8 +36,111 @@
 ///
 /// assert_eq!(iter.collect::<Vec<_>>(), vec![&1, &2, &3, &4, &5, &6, &7, &8, &9]);
 /// ```
+///
+/// # Static alternation
+///
+/// Prefixing the iterators with `static:` expands into a `TupleAltern` instead, with the same order, but without
+/// boxing nor dynamic dispatch. The iterators can have different types, but at most 12 can be given.
+/// ```
+/// use combin_iterator::altern;
+/// let vec1 = vec![1, 4, 7, 9];
+/// let vec2 = vec![2, 5];
+/// let iter = altern!(static: vec1.iter().copied(), vec2.iter().copied(), (3..9).step_by(3));
+///
+/// assert_eq!(iter.collect::<Vec<_>>(), vec![1, 2, 3, 4, 5, 6, 7, 9]);
+/// ```
+///
+/// Which expand into:
+/// ```
+/// let vec1 = vec![1, 4, 7, 9];
+/// let vec2 = vec![2, 5];
+/// let iter = combin_iterator::altern::TupleAltern::new((vec1.iter().copied(), vec2.iter().copied(), (3..9).step_by(3),));
+///
+/// assert_eq!(iter.collect::<Vec<_>>(), vec![1, 2, 3, 4, 5, 6, 7, 9]);
+/// ```
+///
+/// # Chunked alternation
+///
+/// Prefixing the iterators with `chunk: k;` takes up to `k` consecutive elements from each iterator before moving to
+/// the next one (see `VecAltern::with_chunk`).
+/// ```
+/// use combin_iterator::altern;
+/// let vec1 = vec![1, 2, 5, 6, 9];
+/// let vec2 = vec![3, 4, 7];
+/// let iter = altern!(chunk: 2; vec1.iter(), vec2.iter());
+///
+/// assert_eq!(iter.collect::<Vec<_>>(), vec![&1, &2, &3, &4, &5, &6, &7, &9]);
+/// ```
+///
+/// Which expand into:
+/// ```
+/// let vec1 = vec![1, 2, 5, 6, 9];
+/// let vec2 = vec![3, 4, 7];
+/// let iter = combin_iterator::altern!(vec1.iter(), vec2.iter()).with_chunk(2);
+///
+/// assert_eq!(iter.collect::<Vec<_>>(), vec![&1, &2, &3, &4, &5, &6, &7, &9]);
+/// ```
+///
+/// # Send alternation
+///
+/// Prefixing the iterators with `send:` expands into a `SendVecAltern` instead, whose iterators must be `Send`, so
+/// that it can be moved to another thread.
+/// ```
+/// use combin_iterator::altern;
+/// let vec1 = vec![1, 4, 7, 9];
+/// let vec2 = vec![2, 5];
+/// let iter = altern!(send: vec1.into_iter(), vec2.into_iter(), (3..9).step_by(3));
+///
+/// let worker = std::thread::spawn(move || iter.collect::<Vec<_>>());
+/// assert_eq!(worker.join().unwrap(), vec![1, 2, 3, 4, 5, 6, 7, 9]);
+/// ```
+///
+/// Which expand into:
+/// ```
+/// let vec1 = vec![1, 4, 7, 9];
+/// let vec2 = vec![2, 5];
+/// let iter = {
+///    let capacity = 1 + (1 + (1 + 0));
+///    let mut vec_altern = combin_iterator::altern::SendVecAltern::from(Vec::with_capacity(capacity));
+///    vec_altern.add_send(vec1.into_iter());
+///    vec_altern.add_send(vec2.into_iter());
+///    vec_altern.add_send((3..9).step_by(3));
+///    vec_altern
+/// };
+///
+/// let worker = std::thread::spawn(move || iter.collect::<Vec<_>>());
+/// assert_eq!(worker.join().unwrap(), vec![1, 2, 3, 4, 5, 6, 7, 9]);
+/// ```
+///
+/// Both prefixes compose as `chunk: k; send: ...`, which expands into `altern!(send: ...).with_chunk(k)`.
+/// ```
+/// use combin_iterator::altern;
+/// let iter = altern!(chunk: 2; send: vec![1, 2, 5].into_iter(), vec![3, 4].into_iter());
+///
+/// let worker = std::thread::spawn(move || iter.collect::<Vec<_>>());
+/// assert_eq!(worker.join().unwrap(), vec![1, 2, 3, 4, 5]);
+/// ```
 #[macro_export]
 macro_rules! altern {
+    (static: $($params:expr),+ $(,)?) => {
+        $crate::altern::TupleAltern::new(($($params,)+))
+    };
+    (chunk: $chunk:expr; send: $($params:expr),* $(,)?) => {
+        $crate::altern!(send: $($params),*).with_chunk($chunk)
+    };
+    (send: $($params:expr),* $(,)?) => {
+        {
+            let capacity = $crate::count_exprs!($($params),*);
+            let mut vec_altern = $crate::altern::SendVecAltern::from(::std::vec::Vec::with_capacity(capacity));
+            $(
+                vec_altern.add_send($params);
+            )*
+            vec_altern
+        }
+    };
+    (chunk: $chunk:expr; $($params:expr),* $(,)?) => {
+        $crate::altern!($($params),*).with_chunk($chunk)
+    };
     ($($params:expr $(,)?)*) => {
         {
             let capacity = $crate::count_exprs!($($params),*);
@@ -50,6 +153,242 @@ macro_rules! altern {
     };
 }
 
+/// The `merge!` macro provides a convenient syntax for creating a `VecMerge` iterator.
+///
+/// # Syntax
+///
+/// The macro takes a variable number of sorted iterator expressions, separated by commas, and adds each of them to
+/// a `VecMerge` with the `add` method. The elements are compared with `Ord`, with a comparison function if the
+/// iterators are prefixed with `by: cmp;`, or with a key if they are prefixed with `by_key: key;`.
+///
+/// # Example
+/// ```
+/// use combin_iterator::merge;
+/// let vec1 = vec![1, 4, 7];
+/// let vec2 = vec![2, 3, 9];
+/// let vec3 = vec![5, 6, 8];
+/// let iter = merge!(vec1.iter(), vec2.iter(), vec3.iter());
+///
+/// assert_eq!(iter.collect::<Vec<_>>(), vec![&1, &2, &3, &4, &5, &6, &7, &8, &9]);
+///
+/// let iter = merge!(by_key: |value: &&i32| -**value; vec1.iter().rev(), vec2.iter().rev(), vec3.iter().rev());
+/// assert_eq!(iter.collect::<Vec<_>>(), vec![&9, &8, &7, &6, &5, &4, &3, &2, &1]);
+/// ```
+///
+/// # Expand into
+///
+/// From the code before, the first merge! expand into:
+/// ```
+/// let vec1 = vec![1, 4, 7];
+/// let vec2 = vec![2, 3, 9];
+/// let vec3 = vec![5, 6, 8];
+/// let iter = {
+///    let mut vec_merge = combin_iterator::merge::VecMerge::new();
+///    vec_merge.reserve(1 + (1 + (1 + 0)));
+///    vec_merge.add(vec1.iter());
+///    vec_merge.add(vec2.iter());
+///    vec_merge.add(vec3.iter());
+///    vec_merge
+/// };
+///
+/// assert_eq!(iter.collect::<Vec<_>>(), vec![&1, &2, &3, &4, &5, &6, &7, &8, &9]);
+/// ```
+#[macro_export]
+macro_rules! merge {
+    (@add $vec_merge:expr; $($params:expr),*) => {
+        {
+            let mut vec_merge = $vec_merge;
+            vec_merge.reserve($crate::count_exprs!($($params),*));
+            $(
+                vec_merge.add($params);
+            )*
+            vec_merge
+        }
+    };
+    (by: $cmp:expr; $($params:expr),* $(,)?) => {
+        $crate::merge!(@add $crate::merge::VecMerge::by($cmp); $($params),*)
+    };
+    (by_key: $key:expr; $($params:expr),* $(,)?) => {
+        $crate::merge!(@add $crate::merge::VecMerge::by_key($key); $($params),*)
+    };
+    ($($params:expr),* $(,)?) => {
+        $crate::merge!(@add $crate::merge::VecMerge::new(); $($params),*)
+    };
+}
+
+/// The `zip!` macro provides a convenient syntax for creating a `TupleZip` iterator.
+///
+/// # Syntax
+///
+/// The macro takes up to 12 iterator expressions, of any types, separated by commas. It yields flat tuples of one
+/// element from each of them, until the shortest is exhausted. Prefixing the iterators with `longest:` goes on until
+/// the longest is exhausted instead, and yields tuples of `Option` (see `TupleZipLongest`).
+///
+/// # Example
+/// ```
+/// use combin_iterator::zip;
+/// let names = vec!["a", "b", "c"];
+/// let values = vec![1, 2];
+/// let iter = zip!(names.iter(), values.iter(), 10..);
+///
+/// assert_eq!(iter.collect::<Vec<_>>(), vec![(&"a", &1, 10), (&"b", &2, 11)]);
+///
+/// let iter = zip!(longest: names.iter(), values.iter());
+/// assert_eq!(iter.last(), Some((Some(&"c"), None)));
+/// ```
+///
+/// # Expand into
+///
+/// From the code before, the first zip! expand into:
+/// ```
+/// let names = vec!["a", "b", "c"];
+/// let values = vec![1, 2];
+/// let iter = combin_iterator::zip::TupleZip::new((names.iter(), values.iter(), 10..,));
+///
+/// assert_eq!(iter.collect::<Vec<_>>(), vec![(&"a", &1, 10), (&"b", &2, 11)]);
+/// ```
+#[macro_export]
+macro_rules! zip {
+    (longest: $($params:expr),+ $(,)?) => {
+        $crate::zip::TupleZipLongest::new(($($params,)+))
+    };
+    ($($params:expr),+ $(,)?) => {
+        $crate::zip::TupleZip::new(($($params,)+))
+    };
+}
+
+/// The `product!` macro provides a convenient syntax for creating a `TupleProduct` iterator.
+///
+/// # Syntax
+///
+/// The macro takes up to 12 iterator expressions, of any types, separated by commas. The iterators and their
+/// elements must be `Clone`. It yields a flat tuple for every combination of one element of each of them, in
+/// lexicographic order.
+///
+/// # Example
+/// ```
+/// use combin_iterator::product;
+/// let iter = product!(["debug", "release"].into_iter(), 1..3);
+///
+/// assert_eq!(iter.collect::<Vec<_>>(), vec![("debug", 1), ("debug", 2), ("release", 1), ("release", 2)]);
+/// ```
+///
+/// # Expand into
+///
+/// From the code before, product! expand into:
+/// ```
+/// let iter = combin_iterator::product::TupleProduct::new((["debug", "release"].into_iter(), 1..3,));
+///
+/// assert_eq!(iter.collect::<Vec<_>>(), vec![("debug", 1), ("debug", 2), ("release", 1), ("release", 2)]);
+/// ```
+#[macro_export]
+macro_rules! product {
+    ($($params:expr),+ $(,)?) => {
+        $crate::product::TupleProduct::new(($($params,)+))
+    };
+}
+
+/// The `diagonal!` macro provides a convenient syntax for creating a `VecDiagonal` iterator.
+///
+/// # Syntax
+///
+/// The macro takes a variable number of iterator expressions, separated by commas, possibly infinite, and adds each
+/// of them to a `VecDiagonal` with the `add` method. Their elements must be `Clone`.
+///
+/// # Example
+/// ```
+/// use combin_iterator::diagonal;
+/// let iter = diagonal!(0.., (0..).map(|x| x * 10));
+///
+/// assert_eq!(iter.take(4).collect::<Vec<_>>(), vec![vec![0, 0], vec![0, 10], vec![1, 0], vec![0, 20]]);
+/// ```
+///
+/// # Expand into
+///
+/// From the code before, diagonal! expand into:
+/// ```
+/// let iter = {
+///    let capacity = 1 + (1 + 0);
+///    let mut vec_diagonal = combin_iterator::diagonal::VecDiagonal::with_capacity(capacity);
+///    vec_diagonal.add(0..);
+///    vec_diagonal.add((0..).map(|x| x * 10));
+///    vec_diagonal
+/// };
+///
+/// assert_eq!(iter.take(4).collect::<Vec<_>>(), vec![vec![0, 0], vec![0, 10], vec![1, 0], vec![0, 20]]);
+/// ```
+#[macro_export]
+macro_rules! diagonal {
+    ($($params:expr),* $(,)?) => {
+        {
+            let capacity = $crate::count_exprs!($($params),*);
+            let mut vec_diagonal = $crate::diagonal::VecDiagonal::with_capacity(capacity);
+            $(
+                vec_diagonal.add($params);
+            )*
+            vec_diagonal
+        }
+    };
+}
+
+/// The `interleavings!` macro provides a convenient syntax for creating an `Interleavings` iterator.
+///
+/// # Syntax
+///
+/// The macro takes the same iterators as `altern!`, which must be finite, and adds each of them to an
+/// `Interleavings` with the `add` method. Prefixing them with `max_switches: s;` only keeps the interleavings
+/// switching at most `s` times from an iterator to another (see `Interleavings::with_max_switches`).
+///
+/// # Example
+/// ```
+/// use combin_iterator::interleavings;
+/// let thread1 = vec!["lock", "write"];
+/// let thread2 = vec!["read"];
+/// let iter = interleavings!(thread1.iter(), thread2.iter());
+///
+/// assert_eq!(iter.collect::<Vec<_>>(), vec![
+///     vec![&"lock", &"write", &"read"],
+///     vec![&"lock", &"read", &"write"],
+///     vec![&"read", &"lock", &"write"],
+/// ]);
+///
+/// let iter = interleavings!(max_switches: 1; thread1.iter(), thread2.iter());
+/// assert_eq!(iter.count(), 2);
+/// ```
+///
+/// # Expand into
+///
+/// From the code before, the first interleavings! expand into:
+/// ```
+/// let thread1 = vec!["lock", "write"];
+/// let thread2 = vec!["read"];
+/// let iter = {
+///    let capacity = 1 + (1 + 0);
+///    let mut interleavings = combin_iterator::interleavings::Interleavings::with_capacity(capacity);
+///    interleavings.add(thread1.iter());
+///    interleavings.add(thread2.iter());
+///    interleavings
+/// };
+///
+/// assert_eq!(iter.count(), 3);
+/// ```
+#[macro_export]
+macro_rules! interleavings {
+    (max_switches: $switches:expr; $($params:expr),* $(,)?) => {
+        $crate::interleavings!($($params),*).with_max_switches($switches)
+    };
+    ($($params:expr),* $(,)?) => {
+        {
+            let capacity = $crate::count_exprs!($($params),*);
+            let mut interleavings = $crate::interleavings::Interleavings::with_capacity(capacity);
+            $(
+                interleavings.add($params);
+            )*
+            interleavings
+        }
+    };
+}
+
 mod __private {
     #[macro_export]
     #[doc(hidden)]
@@ -61,8 +400,320 @@ mod __private {
     }
 }
 
+/// Implements `Iterator`, `SourceTagged`, `DoubleEndedIterator` and `ExactSizeIterator` for `TupleAltern` over tuples of
+/// every arity up to the number of `(index Iter)` pairs given.
+macro_rules! impl_tuple_altern {
+    (@acc [$($done:tt)*] $next:tt $($rest:tt)*) => {
+        impl_tuple_altern!(@impl $($done)* $next);
+        impl_tuple_altern!(@acc [$($done)* $next] $($rest)*);
+    };
+    (@acc [$($done:tt)*]) => {};
+    (@impl $(($index:tt $iter:ident))+) => {
+        impl<A, $($iter),+> Iterator for $crate::altern::TupleAltern<($($iter,)+)>
+        where
+            $($iter: Iterator<Item = A>),+
+        {
+            type Item = A;
+
+            fn next(&mut self) -> Option<A> {
+                $crate::altern::SourceTagged::next_tagged(self).map(|(_, value)| value)
+            }
+
+            fn size_hint(&self) -> (usize, Option<usize>) {
+                let hints = [$(
+                    if self.exhausted & (1 << $index) == 0 { self.iters.$index.size_hint() } else { (0, Some(0)) }
+                ),+];
+                hints.iter().fold((0, Some(0)), |(lower, upper), hint| {
+                    (lower.saturating_add(hint.0), upper.zip(hint.1).and_then(|(u1, u2)| u1.checked_add(u2)))
+                })
+            }
+        }
+
+        impl<A, $($iter),+> $crate::altern::SourceTagged for $crate::altern::TupleAltern<($($iter,)+)>
+        where
+            $($iter: Iterator<Item = A>),+
+        {
+            fn source_count(&self) -> usize {
+                $crate::count_exprs!($($index),+)
+            }
+
+            fn next_tagged(&mut self) -> Option<(usize, A)> {
+                const N: usize = $crate::count_exprs!($($index),+);
+                for _ in 0..N {
+                    let index = self.current;
+                    self.current = if index + 1 == N { 0 } else { index + 1 };
+                    if self.exhausted & (1 << index) == 0 {
+                        let next = match index {
+                            $($index => self.iters.$index.next(),)+
+                            _ => unreachable!(),
+                        };
+                        match next {
+                            Some(value) => return Some((index, value)),
+                            None => self.exhausted |= 1 << index,
+                        }
+                    }
+                }
+                None
+            }
+        }
+
+        impl<A, $($iter),+> DoubleEndedIterator for $crate::altern::TupleAltern<($($iter,)+)>
+        where
+            $($iter: DoubleEndedIterator<Item = A> + ExactSizeIterator),+
+        {
+            fn next_back(&mut self) -> Option<A> {
+                const N: usize = $crate::count_exprs!($($index),+);
+                let lens = [$(
+                    if self.exhausted & (1 << $index) == 0 { self.iters.$index.len() } else { 0 }
+                ),+];
+                let mut last = None;
+                let mut max_len = 0;
+                let mut index = self.current;
+                for _ in 0..N {
+                    if lens[index] > 0 && lens[index] >= max_len {
+                        max_len = lens[index];
+                        last = Some(index);
+                    }
+                    index = if index + 1 == N { 0 } else { index + 1 };
+                }
+                match last? {
+                    $($index => self.iters.$index.next_back(),)+
+                    _ => unreachable!(),
+                }
+            }
+        }
+
+        impl<A, $($iter),+> ExactSizeIterator for $crate::altern::TupleAltern<($($iter,)+)>
+        where
+            $($iter: ExactSizeIterator<Item = A>),+ {}
+    };
+    ($($pairs:tt)+) => {
+        impl_tuple_altern!(@acc [] $($pairs)+);
+    };
+}
+pub(crate) use impl_tuple_altern;
+
+/// Implements `Iterator`, `DoubleEndedIterator` and `ExactSizeIterator` for `TupleZip` and `TupleZipLongest` over
+/// tuples of every arity up to the number of `(index Iter)` pairs given.
+macro_rules! impl_tuple_zip {
+    (@acc [$($done:tt)*] $next:tt $($rest:tt)*) => {
+        impl_tuple_zip!(@impl $($done)* $next);
+        impl_tuple_zip!(@acc [$($done)* $next] $($rest)*);
+    };
+    (@acc [$($done:tt)*]) => {};
+    (@impl $(($index:tt $iter:ident))+) => {
+        impl<$($iter),+> Iterator for $crate::zip::TupleZip<($($iter,)+)>
+        where
+            $($iter: Iterator),+
+        {
+            type Item = ($($iter::Item,)+);
+
+            fn next(&mut self) -> Option<Self::Item> {
+                Some(($(self.iters.$index.next()?,)+))
+            }
+
+            fn size_hint(&self) -> (usize, Option<usize>) {
+                $crate::zip::min_hint([$(self.iters.$index.size_hint()),+])
+            }
+        }
+
+        impl<$($iter),+> DoubleEndedIterator for $crate::zip::TupleZip<($($iter,)+)>
+        where
+            $($iter: DoubleEndedIterator + ExactSizeIterator),+
+        {
+            fn next_back(&mut self) -> Option<Self::Item> {
+                let len = self.len();
+                $(
+                    let extra = self.iters.$index.len() - len;
+                    if extra > 0 {
+                        self.iters.$index.nth_back(extra - 1);
+                    }
+                )+
+                if len == 0 {
+                    return None;
+                }
+                Some(($(self.iters.$index.next_back()?,)+))
+            }
+        }
+
+        impl<$($iter),+> ExactSizeIterator for $crate::zip::TupleZip<($($iter,)+)>
+        where
+            $($iter: ExactSizeIterator),+ {}
+
+        impl<$($iter),+> Iterator for $crate::zip::TupleZipLongest<($($iter,)+)>
+        where
+            $($iter: Iterator),+
+        {
+            type Item = ($(Option<$iter::Item>,)+);
+
+            fn next(&mut self) -> Option<Self::Item> {
+                const ALL: u16 = (1 << $crate::count_exprs!($($index),+)) - 1;
+                if self.exhausted == ALL {
+                    return None;
+                }
+                let next = ($(
+                    if self.exhausted & (1 << $index) == 0 {
+                        let next = self.iters.$index.next();
+                        if next.is_none() {
+                            self.exhausted |= 1 << $index;
+                        }
+                        next
+                    } else {
+                        None
+                    },
+                )+);
+                (self.exhausted != ALL).then_some(next)
+            }
+
+            fn size_hint(&self) -> (usize, Option<usize>) {
+                $crate::zip::max_hint([$(
+                    if self.exhausted & (1 << $index) == 0 { self.iters.$index.size_hint() } else { (0, Some(0)) }
+                ),+])
+            }
+        }
+
+        impl<$($iter),+> DoubleEndedIterator for $crate::zip::TupleZipLongest<($($iter,)+)>
+        where
+            $($iter: DoubleEndedIterator + ExactSizeIterator),+
+        {
+            fn next_back(&mut self) -> Option<Self::Item> {
+                let lens = [$(
+                    if self.exhausted & (1 << $index) == 0 { self.iters.$index.len() } else { 0 }
+                ),+];
+                let len = lens.iter().copied().max().unwrap_or(0);
+                if len == 0 {
+                    return None;
+                }
+                Some(($(
+                    if lens[$index] == len { self.iters.$index.next_back() } else { None },
+                )+))
+            }
+        }
+
+        impl<$($iter),+> ExactSizeIterator for $crate::zip::TupleZipLongest<($($iter,)+)>
+        where
+            $($iter: ExactSizeIterator),+ {}
+    };
+    ($($pairs:tt)+) => {
+        impl_tuple_zip!(@acc [] $($pairs)+);
+    };
+}
+pub(crate) use impl_tuple_zip;
+
+/// Implements `IntoDims`, `Iterator`, `DoubleEndedIterator` and `ExactSizeIterator` for `TupleProduct` over tuples of
+/// every arity up to the number of `(index Iter)` pairs given.
+macro_rules! impl_tuple_product {
+    (@acc [$($done:tt)*] $next:tt $($rest:tt)*) => {
+        impl_tuple_product!(@impl $($done)* $next);
+        impl_tuple_product!(@acc [$($done)* $next] $($rest)*);
+    };
+    (@acc [$($done:tt)*]) => {};
+    (@impl $(($index:tt $iter:ident))+) => {
+        impl<$($iter),+> $crate::product::tuple_product::IntoDims for ($($iter,)+)
+        where
+            $($iter: Iterator + Clone),+
+        {
+            type Dims = ($($crate::product::Dim<$iter>,)+);
+
+            fn into_dims(self) -> Self::Dims {
+                ($($crate::product::Dim::new(self.$index),)+)
+            }
+        }
+
+        impl<$($iter),+> $crate::product::Dims for ($($crate::product::Dim<$iter>,)+)
+        where
+            $($iter: Iterator + Clone),+
+        {
+            fn count(&self) -> usize {
+                $crate::count_exprs!($($index),+)
+            }
+
+            fn next_front(&mut self, index: usize) -> bool {
+                match index {
+                    $($index => self.$index.next_front(),)+
+                    _ => unreachable!(),
+                }
+            }
+
+            fn restart_front(&mut self, index: usize) -> bool {
+                match index {
+                    $($index => self.$index.restart_front(),)+
+                    _ => unreachable!(),
+                }
+            }
+
+            fn front_hint(&self, index: usize) -> (usize, Option<usize>) {
+                match index {
+                    $($index => self.$index.front_hint(),)+
+                    _ => unreachable!(),
+                }
+            }
+
+            fn full_hint(&self, index: usize) -> (usize, Option<usize>) {
+                match index {
+                    $($index => self.$index.full_hint(),)+
+                    _ => unreachable!(),
+                }
+            }
+        }
+
+        impl<$($iter),+> $crate::product::DimsBack for ($($crate::product::Dim<$iter>,)+)
+        where
+            $($iter: DoubleEndedIterator + Clone),+
+        {
+            fn next_back(&mut self, index: usize) -> bool {
+                match index {
+                    $($index => self.$index.next_back(),)+
+                    _ => unreachable!(),
+                }
+            }
+
+            fn restart_back(&mut self, index: usize) -> bool {
+                match index {
+                    $($index => self.$index.restart_back(),)+
+                    _ => unreachable!(),
+                }
+            }
+        }
+
+        impl<$($iter),+> Iterator for $crate::product::TupleProduct<($($iter,)+)>
+        where
+            $($iter: Iterator + Clone, $iter::Item: Clone),+
+        {
+            type Item = ($($iter::Item,)+);
+
+            fn next(&mut self) -> Option<Self::Item> {
+                self.odometer.step_front(&mut self.dims).then(|| ($(self.dims.$index.front_value(),)+))
+            }
+
+            fn size_hint(&self) -> (usize, Option<usize>) {
+                self.odometer.size_hint(&self.dims)
+            }
+        }
+
+        impl<$($iter),+> DoubleEndedIterator for $crate::product::TupleProduct<($($iter,)+)>
+        where
+            $($iter: DoubleEndedIterator + ExactSizeIterator + Clone, $iter::Item: Clone),+
+        {
+            fn next_back(&mut self) -> Option<Self::Item> {
+                self.odometer.step_back(&mut self.dims).then(|| ($(self.dims.$index.back_value(),)+))
+            }
+        }
+
+        impl<$($iter),+> ExactSizeIterator for $crate::product::TupleProduct<($($iter,)+)>
+        where
+            $($iter: ExactSizeIterator + Clone, $iter::Item: Clone),+ {}
+    };
+    ($($pairs:tt)+) => {
+        impl_tuple_product!(@acc [] $($pairs)+);
+    };
+}
+pub(crate) use impl_tuple_product;
+
 
 #[cfg(test)]
+// The original tests build their iterators from vectors.
+#[allow(clippy::useless_vec)]
 mod tests {
     #[test]
     fn macro_altern() {
@@ -73,4 +724,114 @@ mod tests {
         let iter = altern!(vec1.iter(), vec2.iter(), vec3.iter());
         assert_eq!(iter.collect::<Vec<_>>(), vec![&1,&2,&3,&4,&5,&6,&7,&8, &9]);
     }
+
+    #[test]
+    fn macro_altern_static() {
+        let vec1 = [1, 4, 7, 9];
+        let vec2 = [2, 5];
+        let vec3 = [3, 6, 8];
+
+        let iter = altern!(static: vec1.iter(), vec2.iter(), vec3.iter());
+        assert_eq!(iter.collect::<Vec<_>>(), vec![&1,&2,&3,&4,&5,&6,&7,&8, &9]);
+
+        let iter = altern!(static: vec1.iter(), vec2.iter(), vec3.iter(),);
+        assert_eq!(iter.rev().collect::<Vec<_>>(), vec![&9,&8,&7,&6,&5,&4,&3,&2, &1]);
+
+        let iter = altern!(static: vec1.iter().copied(), 10..12, vec3.iter().map(|x| x * 10));
+        assert_eq!(iter.collect::<Vec<_>>(), vec![1,10,30,4,11,60,7,80,9]);
+    }
+
+    #[test]
+    fn macro_merge() {
+        let vec1 = [1, 4, 7];
+        let vec2 = [2, 3, 9];
+        let vec3 = [5, 6, 8];
+
+        let iter = merge!(vec1.iter(), vec2.iter(), vec3.iter(),);
+        assert_eq!(iter.collect::<Vec<_>>(), vec![&1,&2,&3,&4,&5,&6,&7,&8,&9]);
+
+        let iter = merge!(by: |a: &&i32, b: &&i32| b.cmp(a); vec1.iter().rev(), vec2.iter().rev(), vec3.iter().rev());
+        assert_eq!(iter.collect::<Vec<_>>(), vec![&9,&8,&7,&6,&5,&4,&3,&2,&1]);
+
+        let iter = merge!(by_key: |pair: &(i32, char)| pair.0; [(1, 'a'), (2, 'b')].into_iter(), [(1, 'c')].into_iter());
+        assert_eq!(iter.map(|(_, value)| value).collect::<String>(), "acb");
+    }
+
+    #[test]
+    fn macro_altern_chunk() {
+        let vec1 = [1, 2, 7, 8];
+        let vec2 = [3, 4, 9];
+        let vec3 = [5, 6];
+
+        let iter = altern!(chunk: 2; vec1.iter(), vec2.iter(), vec3.iter());
+        assert_eq!(iter.collect::<Vec<_>>(), vec![&1,&2,&3,&4,&5,&6,&7,&8,&9]);
+
+        let iter = altern!(chunk: 1 + 1; vec1.iter(), vec2.iter(), vec3.iter(),);
+        assert_eq!(iter.count(), 9);
+    }
+
+    #[test]
+    fn macro_altern_send() {
+        let vec1 = vec![1, 4, 7, 9];
+        let vec2 = vec![2, 5];
+        let vec3 = vec![3, 6, 8];
+
+        let iter = altern!(send: vec1.into_iter(), vec2.into_iter(), vec3.into_iter(),);
+        let worker = std::thread::spawn(move || iter.collect::<Vec<_>>());
+        assert_eq!(worker.join().unwrap(), vec![1,2,3,4,5,6,7,8,9]);
+
+        let iter = altern!(send: 0..2, (10..14).step_by(2)).with_chunk(2);
+        assert_eq!(iter.collect::<Vec<_>>(), vec![0,1,10,12]);
+
+        let iter = altern!(chunk: 1 + 1; send: 0..3, 10..13,);
+        let worker = std::thread::spawn(move || iter.collect::<Vec<_>>());
+        assert_eq!(worker.join().unwrap(), vec![0,1,10,11,2,12]);
+    }
+
+    #[test]
+    fn macro_zip() {
+        let vec1 = [1, 2, 3];
+        let vec2 = ['a', 'b'];
+
+        let iter = zip!(vec1.iter(), vec2.iter(), 10..);
+        assert_eq!(iter.collect::<Vec<_>>(), vec![(&1, &'a', 10), (&2, &'b', 11)]);
+
+        let iter = zip!(longest: vec1.iter(), vec2.iter(),);
+        assert_eq!(iter.collect::<Vec<_>>(), vec![(Some(&1), Some(&'a')), (Some(&2), Some(&'b')), (Some(&3), None)]);
+    }
+
+    #[test]
+    fn macro_product() {
+        let vec1 = [1, 2];
+        let vec2 = ['a', 'b'];
+
+        let iter = product!(vec1.iter(), vec2.iter(), 0..1,);
+        assert_eq!(iter.collect::<Vec<_>>(), vec![(&1, &'a', 0), (&1, &'b', 0), (&2, &'a', 0), (&2, &'b', 0)]);
+    }
+
+    #[test]
+    fn macro_diagonal() {
+        let vec1 = [1, 2];
+
+        let iter = diagonal!(vec1.iter(), [10, 20].iter(),);
+        assert_eq!(iter.collect::<Vec<_>>(), vec![vec![&1, &10], vec![&1, &20], vec![&2, &10], vec![&2, &20]]);
+
+        let iter = diagonal!(0.., 0.., 0..);
+        assert_eq!(iter.take(4).collect::<Vec<_>>(), vec![vec![0, 0, 0], vec![0, 0, 1], vec![0, 1, 0], vec![1, 0, 0]]);
+    }
+
+    #[test]
+    fn macro_interleavings() {
+        let vec1 = [1, 2];
+        let vec2 = [3];
+
+        let iter = interleavings!(vec1.iter(), vec2.iter(),);
+        assert_eq!(iter.collect::<Vec<_>>(), vec![vec![&1, &2, &3], vec![&1, &3, &2], vec![&3, &1, &2]]);
+
+        let iter = interleavings!(max_switches: 0; vec1.iter(), vec2.iter());
+        assert_eq!(iter.count(), 0);
+
+        let iter = interleavings!(max_switches: 1 + 1; vec1.iter(), vec2.iter(), [4].iter());
+        assert_eq!(iter.count_exact(), Some(6));
+    }
 }
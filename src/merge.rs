@@ -0,0 +1,26 @@
+//! This module contains some structure to merge sorted iterators
+//!
+//! Unlike an alternation, which takes the elements by position, a merge takes them by value: if each iterator is
+//! sorted, the merge yields all their elements sorted, like log shards merged by timestamp.
+//!
+//! ## When to use what ?
+//!
+//! - `BiMerge` merges exactly 2 iterators, without any allocation nor dynamic dispatch. It can be nested to merge
+//!   more iterators, and is built from an iterator with `MergeWith::merge_with`.
+//! - `VecMerge` merges as many iterators as you want, and keeps their next elements in a binary heap, so each element
+//!   costs a time logarithmic in the number of iterators. Like `VecAltern`, it is built with `add` or `add_and`, or
+//!   with the `merge!` macro.
+//!
+//! Both compare the elements with `Ord` by default, with a comparison function with `by`, or with a key with `by_key`.
+//! When two elements are equal, the one from the iterator added first comes first, so the merge is stable.
+
+pub mod bi_merge;
+pub mod vec_merge;
+
+pub use bi_merge::{BiMerge, MergeWith};
+pub use vec_merge::VecMerge;
+
+use std::cmp::Ordering;
+
+/// The comparison used by `BiMerge::new` and `VecMerge::new`.
+pub type OrdCmp<A> = fn(&A, &A) -> Ordering;
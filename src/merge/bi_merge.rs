@@ -0,0 +1,227 @@
+//! # BiMerge
+//!
+//! The `bi_merge` module provides an iterator, `BiMerge`, for merging exactly 2 sorted iterators.
+//!
+//! ## Usage
+//!
+//! To use `BiMerge`, create a new instance with `BiMerge::new(Iterator<Item>, Iterator<Item>)`, or from an
+//! `Iterator<Item>`, use `Iterator<Item>::merge_with(Iterator<Item>)`. <br/>
+//! The `next` method will then yield the smallest of the next elements of both iterators, until both are exhausted.
+//! If both iterators are sorted, so is the result. When both elements are equal, the one of the first iterator
+//! comes first.<br/>
+//! To merge elements which are not `Ord`, or in another order, use `BiMerge::by` with a comparison function, or
+//! `BiMerge::by_key` with a key.
+//!
+//! ## Examples
+//!
+//! ```rust
+//! use combin_iterator::merge::BiMerge;
+//! let vec1 = vec![1, 4, 5, 9];
+//! let vec2 = vec![2, 3, 8];
+//!
+//! let iter = BiMerge::new(vec1.iter(), vec2.iter());
+//! assert_eq!(iter.collect::<Vec<_>>(), vec![&1, &2, &3, &4, &5, &8, &9]);
+//!
+//! // You can also build it in a line thanks to the trait:
+//! use combin_iterator::merge::MergeWith;
+//! let iter = vec1.iter().merge_with(vec2.iter());
+//! assert_eq!(iter.collect::<Vec<_>>(), vec![&1, &2, &3, &4, &5, &8, &9]);
+//!
+//! // Merging by timestamp:
+//! let shard1 = vec![(1, "a"), (5, "b")];
+//! let shard2 = vec![(1, "c"), (2, "d")];
+//! let iter = BiMerge::by_key(shard1.into_iter(), shard2.into_iter(), |&(timestamp, _)| timestamp);
+//! assert_eq!(iter.map(|(_, log)| log).collect::<String>(), "acdb");
+//! ```
+//!
+//! ## Notes
+//!
+//! - `BiMerge` does not check that the iterators are sorted: if they are not, it still yields all their elements,
+//!   each time taking the smallest of the next two.
+//! - Merging more than 2 iterators with nested `BiMerge`s costs a comparison per level for each element; `VecMerge`
+//!   costs a time logarithmic in the number of iterators instead.
+
+use std::cmp::Ordering;
+use std::iter::Peekable;
+
+use super::OrdCmp;
+
+/// Trait to convert to a BiMerge iterator
+/// Implemented on Iterator
+pub trait MergeWith<T1: Iterator<Item = A>, A> {
+    /// Create the BiMerge, with self and an other iterator, comparing their elements with `Ord`.
+    fn merge_with<T2: Iterator<Item = A>>(self, other: T2) -> BiMerge<T1, T2, A>
+    where
+        Self: Sized,
+        A: Ord;
+
+    /// Create the BiMerge, with self and an other iterator, comparing their elements with `cmp`.
+    fn merge_by<T2: Iterator<Item = A>, F: FnMut(&A, &A) -> Ordering>(self, other: T2, cmp: F) -> BiMerge<T1, T2, A, F>
+    where
+        Self: Sized;
+
+    /// Create the BiMerge, with self and an other iterator, comparing the keys of their elements.
+    fn merge_by_key<T2: Iterator<Item = A>, K: Ord>(self, other: T2, key: impl FnMut(&A) -> K) -> BiMerge<T1, T2, A, impl FnMut(&A, &A) -> Ordering>
+    where
+        Self: Sized;
+}
+
+impl<T1: Iterator<Item = A>, A> MergeWith<T1, A> for T1 {
+    fn merge_with<T2: Iterator<Item = A>>(self, other: T2) -> BiMerge<T1, T2, A>
+    where
+        Self: Sized,
+        A: Ord {
+            BiMerge::new(self, other)
+    }
+
+    fn merge_by<T2: Iterator<Item = A>, F: FnMut(&A, &A) -> Ordering>(self, other: T2, cmp: F) -> BiMerge<T1, T2, A, F>
+    where
+        Self: Sized {
+            BiMerge::by(self, other, cmp)
+    }
+
+    fn merge_by_key<T2: Iterator<Item = A>, K: Ord>(self, other: T2, key: impl FnMut(&A) -> K) -> BiMerge<T1, T2, A, impl FnMut(&A, &A) -> Ordering>
+    where
+        Self: Sized {
+            BiMerge::by_key(self, other, key)
+    }
+}
+
+/// BiMerge struct, to merge 2 sorted iterators.
+pub struct BiMerge<Iter1, Iter2, Item, F = OrdCmp<Item>>
+where
+    Iter1: Iterator<Item = Item>,
+    Iter2: Iterator<Item = Item>
+{
+    iter1: Peekable<Iter1>,
+    iter2: Peekable<Iter2>,
+    cmp: F,
+}
+
+impl<Iter1, Iter2, Item> BiMerge<Iter1, Iter2, Item>
+where
+    Iter1: Iterator<Item = Item>,
+    Iter2: Iterator<Item = Item>
+{
+    /// Creates a new instance of a `BiMerge` iterator, comparing the elements with `Ord`.
+    pub fn new(iter1: Iter1, iter2: Iter2) -> Self
+    where
+        Item: Ord
+    {
+        Self::by(iter1, iter2, Ord::cmp)
+    }
+
+    /// Creates a new instance of a `BiMerge` iterator, comparing the keys of the elements, computed by `key`.
+    ///
+    /// The key is computed each time two elements are compared.
+    pub fn by_key<K: Ord>(iter1: Iter1, iter2: Iter2, mut key: impl FnMut(&Item) -> K) -> BiMerge<Iter1, Iter2, Item, impl FnMut(&Item, &Item) -> Ordering> {
+        BiMerge::by(iter1, iter2, move |a: &Item, b: &Item| key(a).cmp(&key(b)))
+    }
+}
+
+impl<Iter1, Iter2, Item, F> BiMerge<Iter1, Iter2, Item, F>
+where
+    Iter1: Iterator<Item = Item>,
+    Iter2: Iterator<Item = Item>,
+    F: FnMut(&Item, &Item) -> Ordering
+{
+    /// Creates a new instance of a `BiMerge` iterator, comparing the elements with `cmp`.
+    ///
+    /// # Example
+    /// ```
+    /// use combin_iterator::merge::BiMerge;
+    /// let vec1 = vec![9, 5, 4, 1];
+    /// let vec2 = vec![8, 3, 2];
+    ///
+    /// let iter = BiMerge::by(vec1.iter(), vec2.iter(), |a, b| b.cmp(a));
+    /// assert_eq!(iter.collect::<Vec<_>>(), vec![&9, &8, &5, &4, &3, &2, &1]);
+    /// ```
+    pub fn by(iter1: Iter1, iter2: Iter2, cmp: F) -> Self {
+        BiMerge {
+            iter1: iter1.peekable(),
+            iter2: iter2.peekable(),
+            cmp,
+        }
+    }
+
+    /// Returns whether the next element comes from the first iterator, or `None` if both are exhausted.
+    fn next_is_first(&mut self) -> Option<bool> {
+        match (self.iter1.peek(), self.iter2.peek()) {
+            (Some(value1), Some(value2)) => Some((self.cmp)(value1, value2) != Ordering::Greater),
+            (Some(_), None) => Some(true),
+            (None, Some(_)) => Some(false),
+            (None, None) => None,
+        }
+    }
+}
+
+impl<Iter1, Iter2, Item, F> Iterator for BiMerge<Iter1, Iter2, Item, F>
+where
+    Iter1: Iterator<Item = Item>,
+    Iter2: Iterator<Item = Item>,
+    F: FnMut(&Item, &Item) -> Ordering
+{
+    type Item = Item;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.next_is_first()? { self.iter1.next() } else { self.iter2.next() }
+    }
+
+    /// Returns the sum of the bounds of both iterators.
+    ///
+    /// The lower bound saturates at `usize::MAX`, and the upper bound is `None` if it overflows.
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (lower1, upper1) = self.iter1.size_hint();
+        let (lower2, upper2) = self.iter2.size_hint();
+        (lower1.saturating_add(lower2), upper1.zip(upper2).and_then(|(upper1, upper2)| upper1.checked_add(upper2)))
+    }
+}
+
+impl<Iter1, Iter2, Item, F> ExactSizeIterator for BiMerge<Iter1, Iter2, Item, F>
+where
+    Iter1: ExactSizeIterator<Item = Item>,
+    Iter2: ExactSizeIterator<Item = Item>,
+    F: FnMut(&Item, &Item) -> Ordering {}
+
+#[cfg(test)]
+mod tests {
+    use super::{BiMerge, MergeWith};
+
+    #[test]
+    fn merge() {
+        let iter = BiMerge::new([1, 3, 5, 7].into_iter(), [2, 3, 4].into_iter());
+        assert_eq!(iter.collect::<Vec<_>>(), vec![1, 2, 3, 3, 4, 5, 7]);
+
+        let iter = BiMerge::new(0..0, 0..3);
+        assert_eq!(iter.collect::<Vec<_>>(), vec![0, 1, 2]);
+
+        let iter = (0..3).merge_with(0..0);
+        assert_eq!(iter.collect::<Vec<_>>(), vec![0, 1, 2]);
+    }
+
+    #[test]
+    fn stable() {
+        let iter = BiMerge::by_key([(1, 'a'), (2, 'b')].into_iter(), [(1, 'c'), (2, 'd')].into_iter(), |&(key, _)| key);
+        assert_eq!(iter.map(|(_, value)| value).collect::<String>(), "acbd");
+
+        let iter = [(1, 'c'), (2, 'd')].into_iter().merge_by([(1, 'a'), (2, 'b')].into_iter(), |a, b| a.0.cmp(&b.0));
+        assert_eq!(iter.map(|(_, value)| value).collect::<String>(), "cadb");
+    }
+
+    #[test]
+    fn nested() {
+        let iter = (0..10).step_by(3).merge_with((1..10).step_by(3)).merge_with((2..10).step_by(3));
+        assert_eq!(iter.collect::<Vec<_>>(), (0..10).collect::<Vec<_>>());
+
+        let iter = (0..10).rev().step_by(3).merge_by_key((0..8).rev().step_by(3), |&value| std::cmp::Reverse(value));
+        assert_eq!(iter.collect::<Vec<_>>(), vec![9, 7, 6, 4, 3, 1, 0]);
+    }
+
+    #[test]
+    fn len() {
+        let mut iter = BiMerge::new(0..4, 2..5);
+        assert_eq!(iter.len(), 7);
+        iter.next();
+        assert_eq!(iter.len(), 6);
+    }
+}
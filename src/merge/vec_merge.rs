@@ -0,0 +1,394 @@
+//! # VecMerge
+//!
+//! The `vec_merge` module provides an iterator, `VecMerge`, for merging multiple sorted iterators.
+//!
+//! ## Usage
+//!
+//! To use `VecMerge`, create a new instance with `VecMerge::new()` and add iterators using the `add` method (or
+//! `add_and` for a build pattern), or use the macro `merge`.
+//! The `next` method will then yield the smallest of the next elements of all the iterators, until all are exhausted.
+//! If the iterators are sorted, so is the result. When several elements are equal, the one of the iterator added
+//! first comes first.<br/>
+//! To merge elements which are not `Ord`, or in another order, create it with `VecMerge::by` and a comparison
+//! function, or with `VecMerge::by_key` and a key.
+//!
+//! ## Examples
+//!
+//! ```rust
+//! use combin_iterator::merge::VecMerge;
+//! let vec1 = vec![1, 4, 9];
+//! let vec2 = vec![2, 5];
+//! let vec3 = vec![3, 6, 7, 8];
+//!
+//! let mut iter = VecMerge::new();
+//! iter.add(vec1.iter());
+//! iter.add(vec2.iter());
+//! iter.add(vec3.iter());
+//! assert_eq!(iter.collect::<Vec<_>>(), vec![&1, &2, &3, &4, &5, &6, &7, &8, &9]);
+//!
+//! // You can also use a build pattern:
+//! let iter = VecMerge::new().add_and(vec1.iter()).add_and(vec2.iter()).add_and(vec3.iter());
+//! assert_eq!(iter.collect::<Vec<_>>(), vec![&1, &2, &3, &4, &5, &6, &7, &8, &9]);
+//!
+//! // Or the `merge!` macro:
+//! use combin_iterator::merge;
+//! let iter = merge!(vec1.iter(), vec2.iter(), vec3.iter());
+//! assert_eq!(iter.collect::<Vec<_>>(), vec![&1, &2, &3, &4, &5, &6, &7, &8, &9]);
+//! ```
+//!
+//! ## Notes
+//!
+//! - The next element of each iterator is kept in a binary heap, so each element costs a time logarithmic in the
+//!   number of iterators.
+//! - An iterator is only advanced when its previous element is yielded, and added iterators are first advanced by
+//!   the next call to `next`.
+//! - If all the iterators have the same type, `VecMerge::from(Vec<I>)` stores them without boxing. `VecMerge` then
+//!   implements `ExactSizeIterator` if the iterators do.
+//! - `VecMerge` implements `SourceTagged`, to know from which iterator each element comes.
+
+use std::cmp::Ordering;
+use std::marker::PhantomData;
+
+use super::OrdCmp;
+use crate::altern::SourceTagged;
+use crate::Marker;
+
+/// Struct to merge several sorted iterators
+///
+/// The elements are yielded in the order of the comparison `F` (`Ord::cmp` by default, see `by` and `by_key`), and
+/// equal elements in the order their iterators were added. `I` is the type of the iterators, boxed by default (see
+/// the module `altern`).
+pub struct VecMerge<'a, A, I = Box<dyn Iterator<Item = A> + 'a>, F = OrdCmp<A>>
+where
+    I: Iterator<Item = A>
+{
+    iters: Vec<Option<I>>,
+    heap: Vec<(A, usize)>,
+    primed: usize,
+    cmp: F,
+    _marker: Marker<'a, A>,
+}
+
+impl<'a, A: Ord> VecMerge<'a, A> {
+    /// Creates a new instance of a `VecMerge` iterator, comparing the elements with `Ord`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates an empty `VecMerge` comparing the elements with `Ord`, with room for `capacity` iterators and their
+    /// next elements.
+    pub fn with_capacity(capacity: usize) -> Self {
+        let mut vec_merge = Self::new();
+        vec_merge.reserve(capacity);
+        vec_merge
+    }
+}
+
+impl<'a, A, I> VecMerge<'a, A, I>
+where
+    I: Iterator<Item = A>
+{
+    /// Creates a new instance of a `VecMerge` iterator, comparing the keys of the elements, computed by `key`.
+    ///
+    /// The key is computed each time two elements are compared.
+    ///
+    /// # Example
+    /// ```
+    /// use combin_iterator::merge::VecMerge;
+    /// let shard1 = vec![(1, "a"), (5, "b")];
+    /// let shard2 = vec![(1, "c"), (2, "d")];
+    ///
+    /// let iter = VecMerge::by_key(|&(timestamp, _)| timestamp).add_and(shard1.into_iter()).add_and(shard2.into_iter());
+    /// assert_eq!(iter.map(|(_, log)| log).collect::<String>(), "acdb");
+    /// ```
+    pub fn by_key<K: Ord>(mut key: impl FnMut(&A) -> K) -> VecMerge<'a, A, I, impl FnMut(&A, &A) -> Ordering> {
+        VecMerge::by(move |a: &A, b: &A| key(a).cmp(&key(b)))
+    }
+}
+
+impl<'a, A, F> VecMerge<'a, A, Box<dyn Iterator<Item = A> + 'a>, F>
+where
+    F: FnMut(&A, &A) -> Ordering
+{
+    /// Adds an iterator to the `VecMerge` instance.
+    ///
+    /// # Arguments
+    ///
+    /// * `iterator` - An iterator of elements of type `A`, sorted like the other iterators.
+    ///
+    /// # Returns
+    ///
+    /// The updated `VecMerge` instance with the added iterator, to use like a builder.
+    pub fn add_and(mut self, iterator: impl Iterator<Item = A> + 'a) -> Self {
+        self.push(Box::new(iterator));
+        self
+    }
+
+    /// Adds an iterator to the `VecMerge` instance.
+    ///
+    /// # Arguments
+    ///
+    /// * `iterator` - An iterator of elements of type `A`, sorted like the other iterators.
+    pub fn add(&mut self, iterator: impl Iterator<Item = A> + 'a) {
+        self.push(Box::new(iterator));
+    }
+}
+
+impl<'a, A, I, F> VecMerge<'a, A, I, F>
+where
+    I: Iterator<Item = A>,
+    F: FnMut(&A, &A) -> Ordering
+{
+    /// Creates a new instance of a `VecMerge` iterator, comparing the elements with `cmp`.
+    ///
+    /// # Example
+    /// ```
+    /// use combin_iterator::merge::VecMerge;
+    /// let vec1 = vec![9, 4, 1];
+    /// let vec2 = vec![5, 2];
+    ///
+    /// let iter = VecMerge::by(|a: &&i32, b: &&i32| b.cmp(a)).add_and(vec1.iter()).add_and(vec2.iter());
+    /// assert_eq!(iter.collect::<Vec<_>>(), vec![&9, &5, &4, &2, &1]);
+    /// ```
+    pub fn by(cmp: F) -> Self {
+        Self {
+            iters: Vec::new(),
+            heap: Vec::new(),
+            primed: 0,
+            cmp,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Reserves the capacity for at least `additional` more iterators, like `vec::reserve` does.
+    pub fn reserve(&mut self, additional: usize) {
+        self.iters.reserve(additional);
+        self.heap.reserve(additional);
+    }
+
+    /// Adds an iterator of the stored type, without boxing it.
+    ///
+    /// # Arguments
+    ///
+    /// * `iterator` - An iterator of type `I`, sorted like the other iterators.
+    pub fn push(&mut self, iterator: I) {
+        self.iters.push(Some(iterator));
+    }
+
+    /// Puts the first element of the iterators added since the last call in the heap.
+    fn prime(&mut self) {
+        while self.primed < self.iters.len() {
+            let source = self.primed;
+            self.primed += 1;
+            match self.iters[source].as_mut().and_then(Iterator::next) {
+                Some(value) => {
+                    self.heap.push((value, source));
+                    self.sift_up(self.heap.len() - 1);
+                },
+                None => self.iters[source] = None,
+            }
+        }
+    }
+
+    /// Returns whether the entry `i` of the heap comes before the entry `j`: it is smaller, or as small and from an
+    /// iterator added before.
+    fn before(&mut self, i: usize, j: usize) -> bool {
+        let ((value1, source1), (value2, source2)) = (&self.heap[i], &self.heap[j]);
+        (self.cmp)(value1, value2).then(source1.cmp(source2)) == Ordering::Less
+    }
+
+    /// Moves up the entry `i` of the heap until its parent comes before it.
+    fn sift_up(&mut self, mut i: usize) {
+        while i > 0 {
+            let parent = (i - 1) / 2;
+            if !self.before(i, parent) {
+                break;
+            }
+            self.heap.swap(i, parent);
+            i = parent;
+        }
+    }
+
+    /// Moves down the entry `i` of the heap until it comes before its children.
+    fn sift_down(&mut self, mut i: usize) {
+        loop {
+            let (left, right) = (2 * i + 1, 2 * i + 2);
+            let mut first = i;
+            if left < self.heap.len() && self.before(left, first) {
+                first = left;
+            }
+            if right < self.heap.len() && self.before(right, first) {
+                first = right;
+            }
+            if first == i {
+                break;
+            }
+            self.heap.swap(i, first);
+            i = first;
+        }
+    }
+}
+
+impl<'a, A, I> Default for VecMerge<'a, A, I>
+where
+    I: Iterator<Item = A>,
+    A: Ord
+{
+    fn default() -> Self {
+        Self::from(Vec::new())
+    }
+}
+
+impl<'a, A, I> From<Vec<I>> for VecMerge<'a, A, I>
+where
+    I: Iterator<Item = A>,
+    A: Ord
+{
+    /// Creates a `VecMerge` over iterators of one concrete type, without boxing them.
+    ///
+    /// ```
+    /// use combin_iterator::merge::VecMerge;
+    /// let iter = VecMerge::from(vec![(0..10).step_by(3), (1..10).step_by(3), (2..10).step_by(3)]);
+    /// assert_eq!(iter.len(), 10);
+    /// assert_eq!(iter.collect::<Vec<_>>(), (0..10).collect::<Vec<_>>());
+    /// ```
+    fn from(iters: Vec<I>) -> Self {
+        let mut vec_merge = Self::by(Ord::cmp as OrdCmp<A>);
+        vec_merge.iters.reserve_exact(iters.len());
+        vec_merge.heap.reserve_exact(iters.len());
+        for iter in iters {
+            vec_merge.push(iter);
+        }
+        vec_merge
+    }
+}
+
+impl<'a, A, I, F> Iterator for VecMerge<'a, A, I, F>
+where
+    I: Iterator<Item = A>,
+    F: FnMut(&A, &A) -> Ordering
+{
+    type Item = A;
+
+    /// Returns the smallest of the next elements of the iterators.
+    fn next(&mut self) -> Option<A> {
+        self.next_tagged().map(|(_, value)| value)
+    }
+
+    /// Counts the next elements already taken from the iterators, waiting in the heap, plus what the iterators have
+    /// left.
+    ///
+    /// The lower bound saturates at `usize::MAX`, and the upper bound is `None` if it overflows.
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iters.iter().flatten().fold((self.heap.len(), Some(self.heap.len())), |(lower, upper), iter| {
+            let hint = iter.size_hint();
+            (lower.saturating_add(hint.0), upper.zip(hint.1).and_then(|(u1, u2)| u1.checked_add(u2)))
+        })
+    }
+}
+
+impl<'a, A, I, F> SourceTagged for VecMerge<'a, A, I, F>
+where
+    I: Iterator<Item = A>,
+    F: FnMut(&A, &A) -> Ordering
+{
+    /// Returns the number of iterators added, including the exhausted ones.
+    fn source_count(&self) -> usize {
+        self.iters.len()
+    }
+
+    /// Returns the next element, with the index of its iterator in the order they were added.
+    fn next_tagged(&mut self) -> Option<(usize, A)> {
+        self.prime();
+        let source = self.heap.first()?.1;
+        let value = match self.iters[source].as_mut().and_then(Iterator::next) {
+            Some(next) => std::mem::replace(&mut self.heap[0].0, next),
+            None => {
+                self.iters[source] = None;
+                self.heap.swap_remove(0).0
+            },
+        };
+        self.sift_down(0);
+        Some((source, value))
+    }
+}
+
+impl<'a, A, I, F> ExactSizeIterator for VecMerge<'a, A, I, F>
+where
+    I: ExactSizeIterator<Item = A>,
+    F: FnMut(&A, &A) -> Ordering {}
+
+#[cfg(test)]
+mod tests {
+    use super::VecMerge;
+    use crate::altern::SourceTagged;
+
+    #[test]
+    fn vec_merge() {
+        let vec1 = [1, 4, 9];
+        let vec2 = [2, 5];
+        let vec3 = [3, 6, 7, 8];
+        let iter = VecMerge::new().add_and(vec1.iter()).add_and(vec2.iter()).add_and(vec3.iter());
+        assert_eq!(iter.collect::<Vec<_>>(), vec![&1, &2, &3, &4, &5, &6, &7, &8, &9]);
+
+        let iter = VecMerge::<i32>::new();
+        assert_eq!(iter.count(), 0);
+    }
+
+    #[test]
+    fn capacity() {
+        let vec1 = [1, 3];
+        let vec2 = [2];
+        let iter = crate::merge!(by: |a: &&i32, b: &&i32| b.cmp(a); vec1.iter().rev(), vec2.iter());
+        assert!(iter.iters.capacity() >= 2 && iter.heap.capacity() >= 2);
+        assert_eq!(iter.collect::<Vec<_>>(), vec![&3, &2, &1]);
+
+        let iter = VecMerge::<i32>::with_capacity(3);
+        assert!(iter.iters.capacity() >= 3 && iter.heap.capacity() >= 3);
+    }
+
+    #[test]
+    fn matches_sort() {
+        // Pseudo-random sorted iterators, with many equal elements.
+        let mut seed = 7u32;
+        let mut random = move || { seed = seed.wrapping_mul(1103515245).wrapping_add(12345); seed >> 16 };
+        for count in 0..12 {
+            let iters = (0..count).map(|_| {
+                let mut values = (0..random() % 20).map(|_| random() % 10).collect::<Vec<_>>();
+                values.sort();
+                values
+            }).collect::<Vec<_>>();
+
+            let mut expected = iters.iter().enumerate().flat_map(|(source, values)| values.iter().map(move |&value| (value, source))).collect::<Vec<_>>();
+            expected.sort();
+            let iter = VecMerge::from(iters.iter().map(|values| values.iter().copied()).collect::<Vec<_>>());
+            assert_eq!(iter.enumerate_sources().map(|(source, value)| (value, source)).collect::<Vec<_>>(), expected);
+        }
+    }
+
+    #[test]
+    fn stable() {
+        let iter = VecMerge::by_key(|&(key, _)| key)
+            .add_and([(1, 'a'), (2, 'b')].into_iter())
+            .add_and([(0, 'c'), (2, 'd')].into_iter())
+            .add_and([(1, 'e'), (2, 'f')].into_iter());
+        assert_eq!(iter.map(|(_, value)| value).collect::<String>(), "caebdf");
+    }
+
+    #[test]
+    fn add_during_iteration() {
+        let mut iter = VecMerge::new();
+        iter.add([1, 3, 5].into_iter());
+        assert_eq!(iter.next(), Some(1));
+        iter.add([2, 3, 4].into_iter());
+        assert_eq!(iter.collect::<Vec<_>>(), vec![2, 3, 3, 4, 5]);
+    }
+
+    #[test]
+    fn len() {
+        let mut iter = VecMerge::from(vec![0..3, 1..5, 5..5]);
+        assert_eq!(iter.len(), 7);
+        iter.next();
+        assert_eq!(iter.len(), 6);
+    }
+}
@@ -0,0 +1,182 @@
+//! This module contains some structure to iterate over the cartesian product of iterators
+//!
+//! The cartesian product yields every combination of one element of each iterator, in lexicographic order: the last
+//! iterator changes the fastest, like the digits of a counter. The iterators must be `Clone`, as each of them is
+//! iterated again for every combination of the elements of the iterators before it, and so must their elements, as
+//! they are part of several combinations.
+//!
+//! ## When to use what ?
+//!
+//! - `TupleProduct` is the product of a tuple of up to 12 iterators of different types, and yields tuples. The
+//!   `product!` macro builds one.
+//! - `VecProduct` is the product of as many iterators of the same type as you want, chosen at runtime, and yields
+//!   `Vec`.
+//!
+//! Both have an exact `size_hint` if the iterators have one, and implement `DoubleEndedIterator` if the iterators
+//! implement it and `ExactSizeIterator`.
+
+pub mod tuple_product;
+pub mod vec_product;
+
+pub use tuple_product::TupleProduct;
+pub use vec_product::VecProduct;
+
+/// One iterator of a product, with its current elements from the front and from the back.
+///
+/// `front` and `back` are clones of `orig`, restarted each time they are exhausted.
+#[doc(hidden)]
+pub struct Dim<I: Iterator> {
+    orig: I,
+    front: I,
+    front_value: Option<I::Item>,
+    back: I,
+    back_value: Option<I::Item>,
+}
+
+impl<I: Iterator + Clone> Dim<I> {
+    pub(crate) fn new(iter: I) -> Self {
+        Self { front: iter.clone(), front_value: None, back: iter.clone(), back_value: None, orig: iter }
+    }
+
+    pub(crate) fn next_front(&mut self) -> bool {
+        self.front_value = self.front.next();
+        self.front_value.is_some()
+    }
+
+    pub(crate) fn restart_front(&mut self) -> bool {
+        self.front = self.orig.clone();
+        self.next_front()
+    }
+
+    pub(crate) fn front_hint(&self) -> (usize, Option<usize>) {
+        self.front.size_hint()
+    }
+
+    pub(crate) fn full_hint(&self) -> (usize, Option<usize>) {
+        self.orig.size_hint()
+    }
+}
+
+impl<I: Iterator> Dim<I>
+where
+    I::Item: Clone
+{
+    pub(crate) fn front_value(&self) -> I::Item {
+        self.front_value.clone().expect("the front of a running product has a value")
+    }
+
+    pub(crate) fn back_value(&self) -> I::Item {
+        self.back_value.clone().expect("the back of a running product has a value")
+    }
+}
+
+impl<I: DoubleEndedIterator + Clone> Dim<I> {
+    pub(crate) fn next_back(&mut self) -> bool {
+        self.back_value = self.back.next_back();
+        self.back_value.is_some()
+    }
+
+    pub(crate) fn restart_back(&mut self) -> bool {
+        self.back = self.orig.clone();
+        self.next_back()
+    }
+}
+
+/// The iterators of a product, indexed from the first (changing the slowest) to the last.
+pub(crate) trait Dims {
+    fn count(&self) -> usize;
+    fn next_front(&mut self, index: usize) -> bool;
+    fn restart_front(&mut self, index: usize) -> bool;
+    fn front_hint(&self, index: usize) -> (usize, Option<usize>);
+    fn full_hint(&self, index: usize) -> (usize, Option<usize>);
+}
+
+/// The iterators of a product, which can also be iterated from the back.
+pub(crate) trait DimsBack: Dims {
+    fn next_back(&mut self, index: usize) -> bool;
+    fn restart_back(&mut self, index: usize) -> bool;
+}
+
+#[derive(Clone, Copy, Default, PartialEq)]
+enum State {
+    #[default]
+    Start,
+    Running,
+    Done,
+}
+
+/// Advances the iterators of a product like the digits of a counter, from the front and from the back.
+///
+/// The number of combinations left is computed from the front, and the ones already yielded from the back are
+/// subtracted from it.
+#[derive(Default)]
+pub(crate) struct Odometer {
+    front: State,
+    back: State,
+    back_taken: usize,
+}
+
+impl Odometer {
+    /// Moves the front to the next combination, returns `false` if there is none.
+    pub(crate) fn step_front(&mut self, dims: &mut impl Dims) -> bool {
+        if self.back_taken > 0 && self.size_hint(dims).1 == Some(0) {
+            self.front = State::Done;
+        }
+        let count = dims.count();
+        let restart = match self.front {
+            State::Done => return false,
+            State::Start => Some(0),
+            State::Running => (0..count).rev().find(|&index| dims.next_front(index)).map(|index| index + 1),
+        };
+        let running = count > 0
+            && restart.is_some_and(|restart| (restart..count).all(|index| dims.restart_front(index)));
+        self.front = if running { State::Running } else { State::Done };
+        running
+    }
+
+    /// Moves the back to the previous combination, returns `false` if there is none.
+    pub(crate) fn step_back(&mut self, dims: &mut impl DimsBack) -> bool {
+        if self.size_hint(dims).1 == Some(0) {
+            self.back = State::Done;
+        }
+        let count = dims.count();
+        let restart = match self.back {
+            State::Done => return false,
+            State::Start => Some(0),
+            State::Running => (0..count).rev().find(|&index| dims.next_back(index)).map(|index| index + 1),
+        };
+        let running = count > 0
+            && restart.is_some_and(|restart| (restart..count).all(|index| dims.restart_back(index)));
+        self.back = if running { State::Running } else { State::Done };
+        self.back_taken += running as usize;
+        running
+    }
+
+    /// Returns the size hint of the product: the sum, for each iterator, of the elements left in it times the number
+    /// of combinations of the iterators after it.
+    pub(crate) fn size_hint(&self, dims: &impl Dims) -> (usize, Option<usize>) {
+        let count = dims.count();
+        let (lower, upper) = match self.front {
+            _ if count == 0 => return (0, Some(0)),
+            State::Done => return (0, Some(0)),
+            State::Start => (0..count).fold((1, Some(1)), |(lower, upper): (usize, Option<usize>), index| {
+                let full = dims.full_hint(index);
+                (lower.saturating_mul(full.0), upper.zip(full.1).and_then(|(u1, u2)| u1.checked_mul(u2)))
+            }),
+            State::Running => {
+                let mut hint = (0usize, Some(0usize));
+                let mut after = (1usize, Some(1usize));
+                for index in (0..count).rev() {
+                    let (left, full) = (dims.front_hint(index), dims.full_hint(index));
+                    hint.0 = hint.0.saturating_add(left.0.saturating_mul(after.0));
+                    hint.1 = hint.1.zip(left.1.zip(after.1).and_then(|(u1, u2)| u1.checked_mul(u2)))
+                        .and_then(|(u1, u2)| u1.checked_add(u2));
+                    after.0 = after.0.saturating_mul(full.0);
+                    after.1 = after.1.zip(full.1).and_then(|(u1, u2)| u1.checked_mul(u2));
+                }
+                hint
+            }
+        };
+        (lower.saturating_sub(self.back_taken), upper.map(|upper| upper.saturating_sub(self.back_taken)))
+    }
+}
@@ -0,0 +1,104 @@
+//! # TupleProduct
+//!
+//! The `tuple_product` module provides an iterator, `TupleProduct`, for the cartesian product of a tuple of
+//! iterators of different types.
+//!
+//! ## Usage
+//!
+//! To use `TupleProduct`, create a new instance with `TupleProduct::new((Iter1, Iter2, ...))`, with up to 12
+//! iterators, or use the `product!` macro.
+//! The `next` method will then yield a tuple of one element of each iterator, for every combination of their
+//! elements in lexicographic order.
+//!
+//! ## Examples
+//!
+//! ```rust
+//! use combin_iterator::product::TupleProduct;
+//! let iter = TupleProduct::new((["debug", "release"].into_iter(), [false, true].into_iter()));
+//! assert_eq!(iter.len(), 4);
+//! assert_eq!(iter.collect::<Vec<_>>(), vec![
+//!     ("debug", false), ("debug", true), ("release", false), ("release", true),
+//! ]);
+//! ```
+
+use super::Odometer;
+use crate::macros::impl_tuple_product;
+
+/// Turns a tuple of iterators into the state of their product.
+#[doc(hidden)]
+pub trait IntoDims {
+    type Dims;
+
+    fn into_dims(self) -> Self::Dims;
+}
+
+/// TupleProduct struct, to iterate over the cartesian product of the iterators of a tuple.
+pub struct TupleProduct<T: IntoDims> {
+    dims: T::Dims,
+    odometer: Odometer,
+}
+
+impl<T: IntoDims> TupleProduct<T> {
+    /// Creates a new instance of a `TupleProduct` iterator.
+    ///
+    /// `TupleProduct` is an iterator for tuples of 1 to 12 iterators, which are `Clone`, as their elements.
+    pub fn new(iters: T) -> Self {
+        Self { dims: iters.into_dims(), odometer: Odometer::default() }
+    }
+}
+
+impl_tuple_product!(
+    (0 I0) (1 I1) (2 I2) (3 I3) (4 I4) (5 I5)
+    (6 I6) (7 I7) (8 I8) (9 I9) (10 I10) (11 I11)
+);
+
+#[cfg(test)]
+mod tests {
+    use super::TupleProduct;
+
+    #[test]
+    fn product() {
+        let iter = TupleProduct::new((0..2, "ab".chars(), [true].into_iter()));
+        assert_eq!(iter.collect::<Vec<_>>(), vec![(0, 'a', true), (0, 'b', true), (1, 'a', true), (1, 'b', true)]);
+
+        let iter = TupleProduct::new((0..3,));
+        assert_eq!(iter.collect::<Vec<_>>(), vec![(0,), (1,), (2,)]);
+
+        let iter = TupleProduct::new((0..3, std::iter::empty::<u8>()));
+        assert_eq!(iter.size_hint(), (0, Some(0)));
+        assert_eq!(iter.count(), 0);
+    }
+
+    #[test]
+    fn twelve() {
+        let mut iter = TupleProduct::new((0..2, 0..2, 0..2, 0..2, 0..2, 0..2, 0..2, 0..2, 0..2, 0..2, 0..2, 0..2));
+        assert_eq!(iter.len(), 4096);
+        assert_eq!(iter.nth(4095), Some((1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1)));
+    }
+
+    #[test]
+    fn reverse() {
+        let iter = TupleProduct::new((0..2, 10..13));
+        assert_eq!(iter.rev().collect::<Vec<_>>(), vec![(1, 12), (1, 11), (1, 10), (0, 12), (0, 11), (0, 10)]);
+
+        let mut iter = TupleProduct::new((0..2, 10..12));
+        assert_eq!(iter.next(), Some((0, 10)));
+        assert_eq!(iter.next_back(), Some((1, 11)));
+        assert_eq!(iter.len(), 2);
+        assert_eq!(iter.next_back(), Some((1, 10)));
+        assert_eq!(iter.next(), Some((0, 11)));
+        assert_eq!(iter.next(), None);
+        assert_eq!(iter.next_back(), None);
+    }
+
+    #[test]
+    fn size_hint() {
+        let mut iter = TupleProduct::new((0..3, (0..4).filter(|x| x % 2 == 0)));
+        assert_eq!(iter.size_hint(), (0, Some(12)));
+        iter.next();
+        assert_eq!(iter.size_hint(), (0, Some(11)));
+
+        let iter = TupleProduct::new((0..3, 0..));
+        assert_eq!(iter.size_hint(), (usize::MAX, None));
+    }
+}
@@ -0,0 +1,240 @@
+//! # VecProduct
+//!
+//! The `vec_product` module provides an iterator, `VecProduct`, for the cartesian product of any number of iterators
+//! of the same type.
+//!
+//! ## Usage
+//!
+//! To use `VecProduct`, create a new instance with `VecProduct::new()` and add iterators using the `add` method (or
+//! `add_and` for a build pattern), or with `VecProduct::from(Vec<I>)`.
+//! The `next` method will then yield a `Vec` of one element of each iterator, in the order they were added, for
+//! every combination of their elements in lexicographic order.
+//!
+//! ## Examples
+//!
+//! ```rust
+//! use combin_iterator::product::VecProduct;
+//! let iter = VecProduct::new().add_and(0..2).add_and(0..3);
+//! assert_eq!(iter.len(), 6);
+//! assert_eq!(iter.collect::<Vec<_>>(), vec![
+//!     vec![0, 0], vec![0, 1], vec![0, 2],
+//!     vec![1, 0], vec![1, 1], vec![1, 2],
+//! ]);
+//!
+//! let iter = VecProduct::from(vec![["a", "b"].into_iter(), ["c", "d"].into_iter()]);
+//! assert_eq!(iter.rev().map(|names| names.concat()).collect::<Vec<_>>(), vec!["bd", "bc", "ad", "ac"]);
+//! ```
+//!
+//! ## Notes
+//!
+//! - A `VecProduct` without iterators yields nothing, and so does a product with an empty iterator.
+//! - Adding an iterator restarts the product from its first combination.
+//! - Unlike the other `Vec` combinators, the iterators are never boxed, as boxed iterators can not be cloned.
+
+use super::{Dim, Dims, DimsBack, Odometer};
+
+/// Struct to iterate over the cartesian product of several iterators of the same type
+pub struct VecProduct<I: Iterator> {
+    dims: Vec<Dim<I>>,
+    odometer: Odometer,
+}
+
+impl<I> VecProduct<I>
+where
+    I: Iterator + Clone,
+    I::Item: Clone
+{
+    /// Creates a new instance of a `VecProduct` iterator.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates an empty `VecProduct` with room for `capacity` iterators, the length of the combinations it yields.
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self { dims: Vec::with_capacity(capacity), odometer: Odometer::default() }
+    }
+
+    /// Adds an iterator to the `VecProduct` instance.
+    ///
+    /// # Returns
+    ///
+    /// The updated `VecProduct` instance with the added iterator, to use like a builder.
+    pub fn add_and(mut self, iterator: I) -> Self {
+        self.add(iterator);
+        self
+    }
+
+    /// Adds an iterator to the `VecProduct` instance, changing faster than the ones added before.
+    ///
+    /// The product restarts from its first combination.
+    pub fn add(&mut self, iterator: I) {
+        self.dims.push(Dim::new(iterator));
+        self.odometer = Odometer::default();
+    }
+}
+
+impl<I> Default for VecProduct<I>
+where
+    I: Iterator + Clone,
+    I::Item: Clone
+{
+    fn default() -> Self {
+        Self::with_capacity(0)
+    }
+}
+
+impl<I> From<Vec<I>> for VecProduct<I>
+where
+    I: Iterator + Clone,
+    I::Item: Clone
+{
+    fn from(iters: Vec<I>) -> Self {
+        Self { dims: iters.into_iter().map(Dim::new).collect(), odometer: Odometer::default() }
+    }
+}
+
+impl<I: Iterator + Clone> Dims for Vec<Dim<I>> {
+    fn count(&self) -> usize {
+        self.len()
+    }
+
+    fn next_front(&mut self, index: usize) -> bool {
+        self[index].next_front()
+    }
+
+    fn restart_front(&mut self, index: usize) -> bool {
+        self[index].restart_front()
+    }
+
+    fn front_hint(&self, index: usize) -> (usize, Option<usize>) {
+        self[index].front_hint()
+    }
+
+    fn full_hint(&self, index: usize) -> (usize, Option<usize>) {
+        self[index].full_hint()
+    }
+}
+
+impl<I: DoubleEndedIterator + Clone> DimsBack for Vec<Dim<I>> {
+    fn next_back(&mut self, index: usize) -> bool {
+        self[index].next_back()
+    }
+
+    fn restart_back(&mut self, index: usize) -> bool {
+        self[index].restart_back()
+    }
+}
+
+impl<I> Iterator for VecProduct<I>
+where
+    I: Iterator + Clone,
+    I::Item: Clone
+{
+    type Item = Vec<I::Item>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.odometer.step_front(&mut self.dims).then(|| self.dims.iter().map(Dim::front_value).collect())
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.odometer.size_hint(&self.dims)
+    }
+}
+
+impl<I> DoubleEndedIterator for VecProduct<I>
+where
+    I: DoubleEndedIterator + ExactSizeIterator + Clone,
+    I::Item: Clone
+{
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.odometer.step_back(&mut self.dims).then(|| self.dims.iter().map(Dim::back_value).collect())
+    }
+}
+
+impl<I> ExactSizeIterator for VecProduct<I>
+where
+    I: ExactSizeIterator + Clone,
+    I::Item: Clone {}
+
+#[cfg(test)]
+mod tests {
+    use super::VecProduct;
+
+    #[test]
+    fn product() {
+        let iter = VecProduct::new().add_and(0..2).add_and(0..2).add_and(0..2);
+        assert_eq!(iter.collect::<Vec<_>>(), vec![
+            vec![0, 0, 0], vec![0, 0, 1], vec![0, 1, 0], vec![0, 1, 1],
+            vec![1, 0, 0], vec![1, 0, 1], vec![1, 1, 0], vec![1, 1, 1],
+        ]);
+
+        let iter = VecProduct::new().add_and(0..3);
+        assert_eq!(iter.collect::<Vec<_>>(), vec![vec![0], vec![1], vec![2]]);
+    }
+
+    #[test]
+    fn empty() {
+        let iter = VecProduct::<std::ops::Range<i32>>::new();
+        assert_eq!(iter.size_hint(), (0, Some(0)));
+        assert_eq!(iter.count(), 0);
+
+        let iter = VecProduct::from(vec![0..2, 0..0, 0..2]);
+        assert_eq!(iter.len(), 0);
+        assert_eq!(iter.count(), 0);
+
+        let mut iter = VecProduct::from(vec![0..2, 0..0]);
+        assert_eq!(iter.next_back(), None);
+    }
+
+    #[test]
+    fn add_restarts() {
+        let mut iter = VecProduct::new().add_and(0..2);
+        assert_eq!(iter.next(), Some(vec![0]));
+        iter.add(5..7);
+        assert_eq!(iter.collect::<Vec<_>>(), vec![vec![0, 5], vec![0, 6], vec![1, 5], vec![1, 6]]);
+    }
+
+    #[test]
+    fn exact_size() {
+        let mut iter = VecProduct::from(vec![0..3, 0..4, 0..2]);
+        for len in (0..=24).rev() {
+            assert_eq!(iter.size_hint(), (len, Some(len)));
+            iter.next();
+        }
+        assert_eq!(iter.next(), None);
+
+        let even = |x: &i32| x % 2 == 0;
+        let iter = VecProduct::from(vec![(0..4).filter(even), (0..3).filter(even)]);
+        assert_eq!(iter.size_hint(), (0, Some(12)));
+    }
+
+    #[test]
+    fn both_ends() {
+        for lens in [vec![1], vec![3, 1], vec![2, 3, 2], vec![1, 4, 1, 3]] {
+            let product = || VecProduct::from(lens.iter().map(|&len| 0..len).collect::<Vec<_>>());
+            let forward = product().collect::<Vec<_>>();
+
+            let mut reversed = product().rev().collect::<Vec<_>>();
+            reversed.reverse();
+            assert_eq!(reversed, forward);
+
+            for turns in 0..=forward.len() {
+                let mut iter = product();
+                let mut front = Vec::new();
+                let mut back = Vec::new();
+                for turn in 0.. {
+                    assert_eq!(iter.len(), forward.len() - front.len() - back.len());
+                    let next = if turn < turns || turn % 2 == 0 { iter.next_back() } else { iter.next() };
+                    match next {
+                        Some(value) if turn < turns || turn % 2 == 0 => back.push(value),
+                        Some(value) => front.push(value),
+                        None => break,
+                    }
+                }
+                back.reverse();
+                front.extend(back);
+                assert_eq!(front, forward);
+            }
+        }
+    }
+}
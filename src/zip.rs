@@ -0,0 +1,37 @@
+//! This module contains some structure to zip iterators together
+//!
+//! Zipping takes one element from each iterator at a time, and yields them together, like `Iterator::zip`, but for
+//! any number of iterators, and without nesting the tuples (`(a, b, c)` rather than `((a, b), c)`).
+//!
+//! ## When to use what ?
+//!
+//! - `TupleZip` zips a tuple of up to 12 iterators of different types, and yields tuples. The `zip!` macro builds one.
+//! - `VecZip` zips as many iterators of the same type as you want, chosen at runtime, and yields `Vec`.
+//!
+//! Both stop with the shortest iterator, like `Iterator::zip`. `TupleZipLongest` (`zip!(longest: ...)`) and
+//! `VecZipLongest` (`VecZip::longest`) go on until the longest iterator is exhausted instead, and yield `None` in
+//! place of the elements of the exhausted iterators.
+
+pub mod tuple_zip;
+pub mod vec_zip;
+
+pub use tuple_zip::{TupleZip, TupleZipLongest};
+pub use vec_zip::{VecZip, VecZipLongest};
+
+/// Returns the smallest of the size hints: the size hint of a zip stopping with the shortest iterator.
+pub(crate) fn min_hint(hints: impl IntoIterator<Item = (usize, Option<usize>)>) -> (usize, Option<usize>) {
+    hints.into_iter().fold((usize::MAX, None), |(lower, upper), hint| {
+        let upper = match (upper, hint.1) {
+            (Some(u1), Some(u2)) => Some(usize::min(u1, u2)),
+            (upper, hint) => upper.or(hint),
+        };
+        (lower.min(hint.0), upper)
+    })
+}
+
+/// Returns the largest of the size hints: the size hint of a zip going on until the longest iterator is exhausted.
+pub(crate) fn max_hint(hints: impl IntoIterator<Item = (usize, Option<usize>)>) -> (usize, Option<usize>) {
+    hints.into_iter().fold((0, Some(0)), |(lower, upper), hint| {
+        (lower.max(hint.0), upper.zip(hint.1).map(|(u1, u2)| u1.max(u2)))
+    })
+}
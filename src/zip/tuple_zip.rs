@@ -0,0 +1,132 @@
+//! # TupleZip
+//!
+//! The `tuple_zip` module provides iterators, `TupleZip` and `TupleZipLongest`, for zipping a tuple of iterators
+//! of different types into flat tuples.
+//!
+//! ## Usage
+//!
+//! To use `TupleZip`, create a new instance with `TupleZip::new((Iter1, Iter2, ...))`, with up to 12 iterators, or
+//! use the `zip!` macro.
+//! The `next` method will then yield a tuple of the next element of each iterator, until one of them is exhausted.<br/>
+//! `TupleZipLongest` (`TupleZipLongest::new` or `zip!(longest: ...)`) yields a tuple of `Option` instead, until all
+//! of them are exhausted.<br/>
+//! Both implement `ExactSizeIterator` if all iterators implement it, and if they additionally implement
+//! `DoubleEndedIterator`, then so do they, yielding the same tuples in the reverse order.
+//!
+//! ## Examples
+//!
+//! ```rust
+//! use combin_iterator::zip::{TupleZip, TupleZipLongest};
+//! let names = vec!["a", "b", "c"];
+//! let values = vec![1, 2];
+//!
+//! let iter = TupleZip::new((names.iter(), values.iter(), 10..));
+//! assert_eq!(iter.collect::<Vec<_>>(), vec![(&"a", &1, 10), (&"b", &2, 11)]);
+//!
+//! let iter = TupleZipLongest::new((names.iter(), values.iter()));
+//! assert_eq!(iter.collect::<Vec<_>>(), vec![(Some(&"a"), Some(&1)), (Some(&"b"), Some(&2)), (Some(&"c"), None)]);
+//! ```
+
+use crate::macros::impl_tuple_zip;
+
+/// TupleZip struct, to zip the iterators of a tuple until the shortest is exhausted.
+pub struct TupleZip<T> {
+    iters: T,
+}
+
+impl<T> TupleZip<T> {
+    /// Creates a new instance of a `TupleZip` iterator.
+    ///
+    /// `TupleZip` is an iterator for tuples of 1 to 12 iterators.
+    pub fn new(iters: T) -> Self {
+        Self { iters }
+    }
+}
+
+/// TupleZipLongest struct, to zip the iterators of a tuple until the longest is exhausted.
+///
+/// An exhausted iterator is never called again.
+pub struct TupleZipLongest<T> {
+    iters: T,
+    exhausted: u16,
+}
+
+impl<T> TupleZipLongest<T> {
+    /// Creates a new instance of a `TupleZipLongest` iterator.
+    ///
+    /// `TupleZipLongest` is an iterator for tuples of 1 to 12 iterators.
+    pub fn new(iters: T) -> Self {
+        Self { iters, exhausted: 0 }
+    }
+}
+
+impl_tuple_zip!(
+    (0 I0) (1 I1) (2 I2) (3 I3) (4 I4) (5 I5)
+    (6 I6) (7 I7) (8 I8) (9 I9) (10 I10) (11 I11)
+);
+
+#[cfg(test)]
+mod tests {
+    use super::{TupleZip, TupleZipLongest};
+
+    #[test]
+    fn shortest() {
+        let iter = TupleZip::new((0..3, "abcd".chars(), vec![true, false, true].into_iter()));
+        assert_eq!(iter.collect::<Vec<_>>(), vec![(0, 'a', true), (1, 'b', false), (2, 'c', true)]);
+
+        let iter = TupleZip::new((0..3,));
+        assert_eq!(iter.collect::<Vec<_>>(), vec![(0,), (1,), (2,)]);
+
+        let iter = TupleZip::new((0..3, std::iter::empty::<u8>()));
+        assert_eq!(iter.count(), 0);
+    }
+
+    #[test]
+    fn longest() {
+        let iter = TupleZipLongest::new((0..3, "a".chars(), 10..12));
+        assert_eq!(iter.collect::<Vec<_>>(), vec![
+            (Some(0), Some('a'), Some(10)),
+            (Some(1), None, Some(11)),
+            (Some(2), None, None),
+        ]);
+    }
+
+    #[test]
+    fn longest_does_not_call_exhausted_again() {
+        let mut calls = 0;
+        let empty = std::iter::from_fn(|| { calls += 1; None::<u8> });
+        let iter = TupleZipLongest::new((0..3, empty));
+        assert_eq!(iter.count(), 3);
+        assert_eq!(calls, 1);
+    }
+
+    #[test]
+    fn twelve() {
+        let iter = TupleZip::new((0..2, 0..2, 0..2, 0..2, 0..2, 0..2, 0..2, 0..2, 0..2, 0..2, 0..2, 0..1));
+        assert_eq!(iter.collect::<Vec<_>>(), vec![(0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0)]);
+    }
+
+    #[test]
+    fn reverse() {
+        let iter = TupleZip::new((0..5, 10..13, 20..24));
+        assert_eq!(iter.rev().collect::<Vec<_>>(), vec![(2, 12, 22), (1, 11, 21), (0, 10, 20)]);
+
+        let mut iter = TupleZipLongest::new((0..3, 10..11, 20..22));
+        assert_eq!(iter.next_back(), Some((Some(2), None, None)));
+        assert_eq!(iter.next(), Some((Some(0), Some(10), Some(20))));
+        assert_eq!(iter.next_back(), Some((Some(1), None, Some(21))));
+        assert_eq!(iter.next_back(), None);
+        assert_eq!(iter.next(), None);
+    }
+
+    #[test]
+    fn size_hint() {
+        let iter = TupleZip::new((0..5, (0..10).filter(|x| x % 2 == 0), 0..));
+        assert_eq!(iter.size_hint(), (0, Some(5)));
+        assert_eq!(TupleZip::new((0.., 1..)).size_hint(), (usize::MAX, None));
+
+        let iter = TupleZipLongest::new((0..5, (0..10).filter(|x| x % 2 == 0)));
+        assert_eq!(iter.size_hint(), (5, Some(10)));
+        assert_eq!(TupleZipLongest::new((0..5, 0..7)).len(), 7);
+    }
+}
@@ -0,0 +1,274 @@
+//! # VecZip
+//!
+//! The `vec_zip` module provides iterators, `VecZip` and `VecZipLongest`, for zipping any number of iterators of the
+//! same type into `Vec`.
+//!
+//! ## Usage
+//!
+//! To use `VecZip`, create a new instance with `VecZip::new()` and add iterators using the `add` method (or `add_and`
+//! for a build pattern), or with `VecZip::from(Vec<I>)`.
+//! The `next` method will then yield a `Vec` of the next element of each iterator, in the order they were added,
+//! until one of them is exhausted.<br/>
+//! `VecZip::longest` turns it into a `VecZipLongest`, which yields a `Vec<Option<A>>` instead, until all of them are
+//! exhausted.
+//!
+//! ## Examples
+//!
+//! ```rust
+//! use combin_iterator::zip::VecZip;
+//! let vec1 = vec![1, 2, 3];
+//! let vec2 = vec![4, 5];
+//! let vec3 = vec![6, 7, 8];
+//!
+//! let iter = VecZip::new().add_and(vec1.iter()).add_and(vec2.iter()).add_and(vec3.iter());
+//! assert_eq!(iter.collect::<Vec<_>>(), vec![vec![&1, &4, &6], vec![&2, &5, &7]]);
+//!
+//! let iter = VecZip::from(vec![vec1.iter(), vec2.iter(), vec3.iter()]).longest();
+//! assert_eq!(iter.last(), Some(vec![Some(&3), None, Some(&8)]));
+//! ```
+//!
+//! ## Notes
+//!
+//! - A `VecZip` without iterators yields nothing.
+//! - If all the iterators have the same type, `VecZip::from(Vec<I>)` stores them without boxing. `VecZip` and
+//!   `VecZipLongest` then implement `ExactSizeIterator` if the iterators do, and `DoubleEndedIterator` if they
+//!   additionally implement it, yielding the same `Vec` in the reverse order.
+
+use std::marker::PhantomData;
+
+use super::{max_hint, min_hint};
+use crate::Marker;
+
+/// Struct to zip several iterators until the shortest is exhausted
+///
+/// Each `Vec` holds the next element of every iterator, in the order they were added, so all of them are advanced
+/// even when one is found exhausted. `I` is the type of the iterators, boxed by default (see the module `altern`).
+pub struct VecZip<'a, A, I = Box<dyn Iterator<Item = A> + 'a>>
+where
+    I: Iterator<Item = A>
+{
+    iters: Vec<I>,
+    _marker: Marker<'a, A>,
+}
+
+/// Struct to zip several iterators until the longest is exhausted, created by `VecZip::longest`.
+///
+/// An exhausted iterator is dropped, and never called again.
+pub struct VecZipLongest<'a, A, I = Box<dyn Iterator<Item = A> + 'a>>
+where
+    I: Iterator<Item = A>
+{
+    iters: Vec<Option<I>>,
+    _marker: Marker<'a, A>,
+}
+
+impl<'a, A> VecZip<'a, A> {
+    /// Creates a new instance of a `VecZip` iterator.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates an empty `VecZip` with room for `capacity` iterators, which is also the length of the `Vec`s it
+    /// yields.
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self::from(Vec::with_capacity(capacity))
+    }
+
+    /// Adds an iterator to the `VecZip` instance.
+    ///
+    /// # Returns
+    ///
+    /// The updated `VecZip` instance with the added iterator, to use like a builder.
+    pub fn add_and(mut self, iterator: impl Iterator<Item = A> + 'a) -> Self {
+        self.push(Box::new(iterator));
+        self
+    }
+
+    /// Adds an iterator to the `VecZip` instance. Its elements come after the ones of the iterators added before.
+    pub fn add(&mut self, iterator: impl Iterator<Item = A> + 'a) {
+        self.push(Box::new(iterator));
+    }
+}
+
+impl<'a, A, I> VecZip<'a, A, I>
+where
+    I: Iterator<Item = A>
+{
+    /// Adds an iterator of the stored type, without boxing it.
+    pub fn push(&mut self, iterator: I) {
+        self.iters.push(iterator);
+    }
+
+    /// Goes on until the longest iterator is exhausted, yielding `None` in place of the elements of the exhausted
+    /// iterators.
+    ///
+    /// # Example
+    /// ```
+    /// use combin_iterator::zip::VecZip;
+    /// let iter = VecZip::from(vec![0..2, 10..11]).longest();
+    /// assert_eq!(iter.collect::<Vec<_>>(), vec![vec![Some(0), Some(10)], vec![Some(1), None]]);
+    /// ```
+    pub fn longest(self) -> VecZipLongest<'a, A, I> {
+        VecZipLongest {
+            iters: self.iters.into_iter().map(Some).collect(),
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<'a, A, I> Default for VecZip<'a, A, I>
+where
+    I: Iterator<Item = A>
+{
+    fn default() -> Self {
+        Self::from(Vec::new())
+    }
+}
+
+impl<'a, A, I> From<Vec<I>> for VecZip<'a, A, I>
+where
+    I: Iterator<Item = A>
+{
+    /// Creates a `VecZip` over iterators of one concrete type, without boxing them.
+    fn from(iters: Vec<I>) -> Self {
+        Self { iters, _marker: PhantomData }
+    }
+}
+
+impl<'a, A, I> Iterator for VecZip<'a, A, I>
+where
+    I: Iterator<Item = A>
+{
+    type Item = Vec<A>;
+
+    fn next(&mut self) -> Option<Vec<A>> {
+        if self.iters.is_empty() {
+            return None;
+        }
+        self.iters.iter_mut().map(Iterator::next).collect()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        if self.iters.is_empty() {
+            return (0, Some(0));
+        }
+        min_hint(self.iters.iter().map(Iterator::size_hint))
+    }
+}
+
+impl<'a, A, I> DoubleEndedIterator for VecZip<'a, A, I>
+where
+    I: DoubleEndedIterator<Item = A> + ExactSizeIterator
+{
+    /// Returns the last `Vec`, first dropping from the back the elements of the iterators longer than the shortest.
+    fn next_back(&mut self) -> Option<Vec<A>> {
+        let len = self.len();
+        for iter in self.iters.iter_mut() {
+            if iter.len() > len {
+                iter.nth_back(iter.len() - len - 1);
+            }
+        }
+        if len == 0 {
+            return None;
+        }
+        self.iters.iter_mut().map(DoubleEndedIterator::next_back).collect()
+    }
+}
+
+impl<'a, A, I> ExactSizeIterator for VecZip<'a, A, I>
+where
+    I: ExactSizeIterator<Item = A> {}
+
+impl<'a, A, I> Iterator for VecZipLongest<'a, A, I>
+where
+    I: Iterator<Item = A>
+{
+    type Item = Vec<Option<A>>;
+
+    fn next(&mut self) -> Option<Vec<Option<A>>> {
+        let mut live = false;
+        let next = self.iters.iter_mut().map(|slot| {
+            let next = slot.as_mut().and_then(Iterator::next);
+            if next.is_none() {
+                *slot = None;
+            }
+            live |= next.is_some();
+            next
+        }).collect();
+        live.then_some(next)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        max_hint(self.iters.iter().flatten().map(Iterator::size_hint))
+    }
+}
+
+impl<'a, A, I> DoubleEndedIterator for VecZipLongest<'a, A, I>
+where
+    I: DoubleEndedIterator<Item = A> + ExactSizeIterator
+{
+    /// Returns the last `Vec`, with the last elements of the longest iterators, and `None` for the others.
+    fn next_back(&mut self) -> Option<Vec<Option<A>>> {
+        let len = self.len();
+        if len == 0 {
+            return None;
+        }
+        Some(self.iters.iter_mut().map(|slot| match slot {
+            Some(iter) if iter.len() == len => iter.next_back(),
+            _ => None,
+        }).collect())
+    }
+}
+
+impl<'a, A, I> ExactSizeIterator for VecZipLongest<'a, A, I>
+where
+    I: ExactSizeIterator<Item = A> {}
+
+#[cfg(test)]
+mod tests {
+    use super::VecZip;
+
+    #[test]
+    fn shortest() {
+        let iter = VecZip::new().add_and(0..3).add_and("abc".chars().map(|c| c as i32)).add_and(10..);
+        assert_eq!(iter.size_hint(), (1, Some(3)));
+        assert_eq!(iter.collect::<Vec<_>>(), vec![vec![0, 97, 10], vec![1, 98, 11], vec![2, 99, 12]]);
+
+        // From the back, the extra elements of the longer iterators are dropped first.
+        let iter = VecZip::from(vec![0..4, 10..12, 20..23]);
+        assert_eq!(iter.rev().collect::<Vec<_>>(), vec![vec![1, 11, 21], vec![0, 10, 20]]);
+
+        let iter = VecZip::<i32>::new();
+        assert_eq!(iter.size_hint(), (0, Some(0)));
+        assert_eq!(iter.count(), 0);
+    }
+
+    #[test]
+    fn longest() {
+        let iter = VecZip::from(vec![0..3, 10..11, 20..22]).longest();
+        assert_eq!(iter.len(), 3);
+        assert_eq!(iter.collect::<Vec<_>>(), vec![
+            vec![Some(0), Some(10), Some(20)],
+            vec![Some(1), None, Some(21)],
+            vec![Some(2), None, None],
+        ]);
+
+        // From the back, only the longest iterators yield an element.
+        let iter = VecZip::from(vec![0..3, 10..11, 20..22]).longest();
+        assert_eq!(iter.rev().collect::<Vec<_>>(), vec![
+            vec![Some(2), None, None],
+            vec![Some(1), None, Some(21)],
+            vec![Some(0), Some(10), Some(20)],
+        ]);
+
+        // An exhausted iterator is not called again.
+        let mut calls = 0;
+        let empty = std::iter::from_fn(|| { calls += 1; None });
+        let iter = VecZip::new().add_and(0..3).add_and(empty).longest();
+        assert_eq!(iter.count(), 3);
+        assert_eq!(calls, 1);
+
+        let iter = VecZip::<i32>::new().longest();
+        assert_eq!(iter.count(), 0);
+    }
+}